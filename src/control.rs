@@ -1,9 +1,11 @@
 //! Hardware control of terminal
 
-use std::fmt;
+use std::{cmp, fmt};
+use std::str::FromStr;
 use std::ops::{Index, IndexMut};
+use std::time::Duration;
 
-use Termios;
+use {ParseError, Termios, input};
 use raw::{cc_t, tcflag_t, self};
 use self::CSIZE::*;
 use self::Char::*;
@@ -11,15 +13,17 @@ use self::Flag::*;
 use traits::{Clear, Contains, Get, GetFrom, Set};
 
 /// Control flags
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 #[repr(C)]
 pub struct Flags(tcflag_t);
 
+flags!(MASKS);
+
 impl fmt::Debug for Flags {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        try!(write!(f, "{:?}", CSIZE::from_raw(self.0 & CSIZE_MASK)));
+        try!(write!(f, "{:?}", CSIZE::from_raw(self.0)));
 
-        for &flag in &FLAGS {
+        for flag in Flag::all() {
             let value = flag.to_raw();
 
             if self.0 & value == value {
@@ -27,29 +31,91 @@ impl fmt::Debug for Flags {
             }
         }
 
+        let unknown = self.unknown_bits();
+
+        if unknown != 0 {
+            try!(write!(f, " | {:#x}", unknown));
+        }
+
         Ok(())
     }
 }
 
-const FLAGS: [Flag; 7] = [
+const FLAGS: [Flag; 6] = [
     CLOCAL,
     CREAD,
-    CRTSCTS,
     CSTOPB,
     HUPCL,
     PARENB,
     PARODD,
 ];
 
+// NB On FreeBSD and macOS, `CRTSCTS` is `CCTS_OFLOW | CRTS_IFLOW`, so `Debug` prints the three
+// of them when it's set
+#[cfg(target_os = "freebsd")]
+const EXTENSIONS: [Flag; 7] = [
+    CCAR_OFLOW,
+    CCTS_OFLOW,
+    CDSR_OFLOW,
+    CDTR_IFLOW,
+    CIGNORE,
+    CRTSCTS,
+    CRTS_IFLOW,
+];
+
+#[cfg(target_os = "linux")]
+const EXTENSIONS: [Flag; 2] = [
+    CMSPAR,
+    CRTSCTS,
+];
+
+#[cfg(target_os = "macos")]
+const EXTENSIONS: [Flag; 7] = [
+    CCAR_OFLOW,
+    CCTS_OFLOW,
+    CDSR_OFLOW,
+    CDTR_IFLOW,
+    CIGNORE,
+    CRTSCTS,
+    CRTS_IFLOW,
+];
+
 /// Standard control flags
-#[derive(Clone, Copy, Debug)]
+///
+/// Platform specific flags are only available on the platforms that support them, use
+/// `Flag::all()` to list the flags supported by the current target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Flag {
+    /// DCD flow control of output
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CCAR_OFLOW,
+    /// CTS flow control of output
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CCTS_OFLOW,
+    /// DSR flow control of output
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CDSR_OFLOW,
+    /// DTR flow control of input
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CDTR_IFLOW,
+    /// Ignore control flags
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CIGNORE,
     /// Ignore modem status lines
     CLOCAL,
+    /// Mark or space (stick) parity
+    #[cfg(target_os = "linux")]
+    CMSPAR,
     /// Enable receiver
     CREAD,
     /// RTS/CTS full-duplex flow control
+    ///
+    /// On FreeBSD and macOS, this is a shorthand for `CCTS_OFLOW | CRTS_IFLOW` and `Flag::all()`
+    /// doesn't list it.
     CRTSCTS,
+    /// RTS flow control of input
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    CRTS_IFLOW,
     /// Send 2 STOP bits
     CSTOPB,
     /// Hang up on last close
@@ -61,11 +127,92 @@ pub enum Flag {
 }
 
 impl Flag {
+    /// Returns all the flags supported by the current target, in the order `Debug` prints them
+    pub fn all() -> Vec<Flag> {
+        FLAGS.iter().chain(EXTENSIONS.iter()).cloned().collect()
+    }
+
+    /// Returns the name of the flag, e.g. `"CREAD"`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCAR_OFLOW => "CCAR_OFLOW",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCTS_OFLOW => "CCTS_OFLOW",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDSR_OFLOW => "CDSR_OFLOW",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDTR_IFLOW => "CDTR_IFLOW",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CIGNORE => "CIGNORE",
+            CLOCAL => "CLOCAL",
+            #[cfg(target_os = "linux")]
+            CMSPAR => "CMSPAR",
+            CREAD => "CREAD",
+            CRTSCTS => "CRTSCTS",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CRTS_IFLOW => "CRTS_IFLOW",
+            CSTOPB => "CSTOPB",
+            HUPCL => "HUPCL",
+            PARENB => "PARENB",
+            PARODD => "PARODD",
+        }
+    }
+
+    /// Returns a short description of the flag
+    pub fn description(&self) -> &'static str {
+        match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCAR_OFLOW => "DCD flow control of output",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCTS_OFLOW => "CTS flow control of output",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDSR_OFLOW => "DSR flow control of output",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDTR_IFLOW => "DTR flow control of input",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CIGNORE => "Ignore control flags",
+            CLOCAL => "Ignore modem status lines",
+            #[cfg(target_os = "linux")]
+            CMSPAR => "Mark or space (stick) parity",
+            CREAD => "Enable receiver",
+            CRTSCTS => "RTS/CTS full-duplex flow control",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CRTS_IFLOW => "RTS flow control of input",
+            CSTOPB => "Send 2 STOP bits",
+            HUPCL => "Hang up on last close",
+            PARENB => "Parity enable",
+            PARODD => "Odd parity, else even",
+        }
+    }
+
+    /// Checks if the flag is specified by POSIX, otherwise it's an extension
+    pub fn is_posix(&self) -> bool {
+        match *self {
+            CLOCAL | CREAD | CSTOPB | HUPCL | PARENB | PARODD => true,
+            _ => false,
+        }
+    }
+
     fn to_raw(&self) -> tcflag_t {
         match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCAR_OFLOW => raw::CCAR_OFLOW,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CCTS_OFLOW => raw::CCTS_OFLOW,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDSR_OFLOW => raw::CDSR_OFLOW,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CDTR_IFLOW => raw::CDTR_IFLOW,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CIGNORE => raw::CIGNORE,
             CLOCAL => raw::CLOCAL,
+            #[cfg(target_os = "linux")]
+            CMSPAR => raw::CMSPAR,
             CREAD => raw::CREAD,
             CRTSCTS => raw::CRTSCTS,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            CRTS_IFLOW => raw::CRTS_IFLOW,
             CSTOPB => raw::CSTOPB,
             HUPCL => raw::HUPCL,
             PARENB => raw::PARENB,
@@ -74,6 +221,20 @@ impl Flag {
     }
 }
 
+impl FromStr for Flag {
+    type Err = ParseError;
+
+    /// Parses the name of the flag, case insensitively (e.g. `"cread"`)
+    fn from_str(s: &str) -> Result<Flag, ParseError> {
+        let name = s.to_uppercase();
+
+        match Flag::all().into_iter().find(|x| x.name() == name) {
+            Some(x) => Ok(x),
+            None => Err(ParseError::new("control flag", s)),
+        }
+    }
+}
+
 impl Clear<Flag> for Termios {
     fn clear(&mut self, flag: Flag) {
         self.cflag.0 &= !flag.to_raw()
@@ -95,7 +256,7 @@ impl Set<Flag> for Termios {
 }
 
 /// Character size
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum CSIZE {
     /// 5 bits (pseudo)
     CS5,
@@ -117,24 +278,33 @@ impl CSIZE {
         }
     }
 
+    // NB `CSIZE` is a two bit field and `CS5`, `CS6`, `CS7` and `CS8` are its four values, so
+    // every `csize & CSIZE_MASK` is known
     fn from_raw(csize: tcflag_t) -> CSIZE {
-        match csize {
+        match csize & CSIZE_MASK {
             raw::CS5 => CS5,
             raw::CS6 => CS6,
             raw::CS7 => CS7,
-            raw::CS8 => CS8,
-            _ => panic!("Unknown CSIZE state: {}", csize),
+            _ => CS8,
         }
     }
 }
 
 const CSIZE_MASK: tcflag_t = raw::CS5 | raw::CS6 | raw::CS7 | raw::CS8;
 
+// Multi-bit fields, on Linux the speeds are also stored in the control flags
+#[cfg(target_os = "linux")]
+const MASKS: tcflag_t = CSIZE_MASK | raw::CBAUD | raw::CBAUDEX | raw::BOTHER | raw::CIBAUD;
+
+// Multi-bit fields
+#[cfg(not(target_os = "linux"))]
+const MASKS: tcflag_t = CSIZE_MASK;
+
 impl Get for Termios {}
 
 impl GetFrom<Termios> for CSIZE {
     fn get_from(termios: &Termios) -> CSIZE {
-        CSIZE::from_raw(termios.cflag.0 & CSIZE_MASK)
+        CSIZE::from_raw(termios.cflag.0)
     }
 }
 
@@ -145,8 +315,278 @@ impl Set<CSIZE> for Termios {
     }
 }
 
+/// How a non-canonical read (`ICANON` cleared) waits for input, encoded in `VMIN` and `VTIME`
+///
+/// `VTIME` is measured in deciseconds, so durations are rounded up to the next decisecond and
+/// must be between 0.1 and 25.5 seconds, and the byte counts can't be zero. The constructors
+/// (e.g. `ReadPolicy::timeout`) reject the values out of range, `set` saturates them instead.
+///
+/// # Examples
+///
+/// ``` no_run
+/// extern crate termios;
+///
+/// use std::io;
+/// use std::time::Duration;
+///
+/// use termios::control::ReadPolicy;
+/// use termios::prelude::*;
+///
+/// fn main() {
+///     let stdin = io::stdin();
+///     let mut termios = stdin.termios().unwrap();
+///     termios.clear(local::Flag::ICANON);
+///     termios.set(ReadPolicy::timeout(Duration::from_millis(500)).unwrap());
+///     stdin.set_termios(&termios, When::Now).unwrap();
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReadPolicy {
+    /// Returns immediately with the available bytes, possibly none (`VMIN = 0, VTIME = 0`)
+    Poll,
+    /// Blocks until at least this many bytes are available (`VMIN > 0, VTIME = 0`)
+    Blocking(u8),
+    /// Returns as soon as a byte is available, or with nothing once the duration elapses
+    /// (`VMIN = 0, VTIME > 0`)
+    Timeout(Duration),
+    /// Blocks until the first byte, then returns once this many bytes are available or the
+    /// duration elapses between two bytes (`VMIN > 0, VTIME > 0`)
+    InterByte(u8, Duration),
+}
+
+impl ReadPolicy {
+    /// Blocks until at least `min` bytes are available, `None` if `min` is zero
+    pub fn blocking(min: u8) -> Option<ReadPolicy> {
+        ReadPolicy::Blocking(min).checked()
+    }
+
+    /// Waits at most `timeout` for a byte, `None` if `timeout` is out of range
+    pub fn timeout(timeout: Duration) -> Option<ReadPolicy> {
+        ReadPolicy::Timeout(timeout).checked()
+    }
+
+    /// Waits for `min` bytes, with an inter-byte `timeout` that starts after the first byte,
+    /// `None` if `min` is zero or `timeout` is out of range
+    pub fn inter_byte(min: u8, timeout: Duration) -> Option<ReadPolicy> {
+        ReadPolicy::InterByte(min, timeout).checked()
+    }
+
+    /// Checks if the policy can be encoded in `VMIN` and `VTIME` without saturating
+    pub fn is_valid(&self) -> bool {
+        match *self {
+            ReadPolicy::Poll => true,
+            ReadPolicy::Blocking(min) => min != 0,
+            ReadPolicy::Timeout(timeout) => deciseconds(timeout).is_some(),
+            ReadPolicy::InterByte(min, timeout) => min != 0 && deciseconds(timeout).is_some(),
+        }
+    }
+
+    fn checked(self) -> Option<ReadPolicy> {
+        if self.is_valid() { Some(self) } else { None }
+    }
+
+    fn from_raw(min: cc_t, time: cc_t) -> ReadPolicy {
+        let timeout = Duration::from_millis(100 * time as u64);
+
+        match (min, time) {
+            (0, 0) => ReadPolicy::Poll,
+            (min, 0) => ReadPolicy::Blocking(min),
+            (0, _) => ReadPolicy::Timeout(timeout),
+            (min, _) => ReadPolicy::InterByte(min, timeout),
+        }
+    }
+
+    // Returns the `VMIN` and `VTIME` values, saturating the values out of range
+    fn to_raw(&self) -> (cc_t, cc_t) {
+        match *self {
+            ReadPolicy::Poll => (0, 0),
+            ReadPolicy::Blocking(min) => (cmp::max(min, 1), 0),
+            ReadPolicy::Timeout(timeout) => (0, saturating_deciseconds(timeout)),
+            ReadPolicy::InterByte(min, timeout) => {
+                (cmp::max(min, 1), saturating_deciseconds(timeout))
+            },
+        }
+    }
+}
+
+// Rounds `duration` up to the next decisecond, `None` if it doesn't fit in `VTIME`
+fn deciseconds(duration: Duration) -> Option<cc_t> {
+    if duration.as_secs() > 25 {
+        return None;
+    }
+
+    let nanos = duration.as_secs() * 1_000_000_000 + duration.subsec_nanos() as u64;
+
+    match (nanos + 99_999_999) / 100_000_000 {
+        0 => None,
+        time if time > 255 => None,
+        time => Some(time as cc_t),
+    }
+}
+
+// Like `deciseconds`, but clamps `duration` between 0.1 and 25.5 seconds
+fn saturating_deciseconds(duration: Duration) -> cc_t {
+    match deciseconds(duration) {
+        Some(time) => time,
+        None if duration.as_secs() == 0 && duration.subsec_nanos() == 0 => 1,
+        None => 255,
+    }
+}
+
+impl GetFrom<Termios> for ReadPolicy {
+    fn get_from(termios: &Termios) -> ReadPolicy {
+        ReadPolicy::from_raw(termios.cc[VMIN], termios.cc[VTIME])
+    }
+}
+
+impl Set<ReadPolicy> for Termios {
+    fn set(&mut self, policy: ReadPolicy) {
+        let (min, time) = policy.to_raw();
+
+        self.cc[VMIN] = min;
+        self.cc[VTIME] = time;
+    }
+}
+
+/// Parity bit, a typed view of `PARENB`, `PARODD` and `CMSPAR`
+///
+/// Only the control flags are touched, checking the parity of the received bytes is up to
+/// `input::Flag::INPCK`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Parity {
+    /// No parity bit
+    None,
+    /// Odd parity
+    Odd,
+    /// Even parity
+    Even,
+    /// The parity bit is always set (`CMSPAR` with `PARODD`)
+    #[cfg(target_os = "linux")]
+    Mark,
+    /// The parity bit is always cleared (`CMSPAR` without `PARODD`)
+    #[cfg(target_os = "linux")]
+    Space,
+}
+
+#[cfg(target_os = "linux")]
+const PARITY_MASK: tcflag_t = raw::PARENB | raw::PARODD | raw::CMSPAR;
+
+#[cfg(not(target_os = "linux"))]
+const PARITY_MASK: tcflag_t = raw::PARENB | raw::PARODD;
+
+impl Parity {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            Parity::None => 0,
+            Parity::Odd => raw::PARENB | raw::PARODD,
+            Parity::Even => raw::PARENB,
+            #[cfg(target_os = "linux")]
+            Parity::Mark => raw::PARENB | raw::PARODD | raw::CMSPAR,
+            #[cfg(target_os = "linux")]
+            Parity::Space => raw::PARENB | raw::CMSPAR,
+        }
+    }
+}
+
+impl GetFrom<Termios> for Parity {
+    fn get_from(termios: &Termios) -> Parity {
+        let cflag = termios.cflag.0;
+
+        if cflag & raw::PARENB == 0 {
+            return Parity::None;
+        }
+
+        match cflag & PARITY_MASK {
+            #[cfg(target_os = "linux")]
+            parity if parity & raw::CMSPAR != 0 => {
+                if parity & raw::PARODD != 0 { Parity::Mark } else { Parity::Space }
+            },
+            parity if parity & raw::PARODD != 0 => Parity::Odd,
+            _ => Parity::Even,
+        }
+    }
+}
+
+impl Set<Parity> for Termios {
+    fn set(&mut self, parity: Parity) {
+        self.cflag.0 &= !PARITY_MASK;
+        self.cflag.0 |= parity.to_raw();
+    }
+}
+
+/// Number of stop bits, a typed view of `CSTOPB`
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StopBits {
+    /// One stop bit
+    One,
+    /// Two stop bits (`CSTOPB`)
+    Two,
+}
+
+impl GetFrom<Termios> for StopBits {
+    fn get_from(termios: &Termios) -> StopBits {
+        if termios.cflag.0 & raw::CSTOPB == 0 { StopBits::One } else { StopBits::Two }
+    }
+}
+
+impl Set<StopBits> for Termios {
+    fn set(&mut self, stop_bits: StopBits) {
+        match stop_bits {
+            StopBits::One => self.cflag.0 &= !raw::CSTOPB,
+            StopBits::Two => self.cflag.0 |= raw::CSTOPB,
+        }
+    }
+}
+
+/// Flow control, a typed view of `CRTSCTS`, `IXON` and `IXOFF`
+///
+/// `get` reports `Hardware` if `CRTSCTS` is set, otherwise `Software` if either `IXON` or
+/// `IXOFF` is set.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FlowControl {
+    /// No flow control
+    None,
+    /// XON/XOFF characters (`VSTART`/`VSTOP`), for both input and output (`IXON` and `IXOFF`)
+    Software,
+    /// RTS/CTS lines (`CRTSCTS`)
+    Hardware,
+}
+
+impl GetFrom<Termios> for FlowControl {
+    fn get_from(termios: &Termios) -> FlowControl {
+        if termios.cflag.0 & raw::CRTSCTS != 0 {
+            FlowControl::Hardware
+        } else if termios.iflag.contains_any(input::Flag::IXON | input::Flag::IXOFF) {
+            FlowControl::Software
+        } else {
+            FlowControl::None
+        }
+    }
+}
+
+impl Set<FlowControl> for Termios {
+    fn set(&mut self, flow_control: FlowControl) {
+        let software = input::Flag::IXON | input::Flag::IXOFF;
+
+        match flow_control {
+            FlowControl::None => {
+                self.cflag.0 &= !raw::CRTSCTS;
+                self.iflag.remove(software);
+            },
+            FlowControl::Software => {
+                self.cflag.0 &= !raw::CRTSCTS;
+                self.iflag.insert(software);
+            },
+            FlowControl::Hardware => {
+                self.cflag.0 |= raw::CRTSCTS;
+                self.iflag.remove(software);
+            },
+        }
+    }
+}
+
 /// Control chars
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 #[repr(C)]
 pub struct Chars([cc_t; raw::NCCS as usize]);
 
@@ -164,17 +604,40 @@ impl IndexMut<Char> for Chars {
     }
 }
 
+impl Chars {
+    /// Returns the value of `char`, or `None` if it's disabled
+    ///
+    /// `VMIN` and `VTIME` hold counts rather than characters, so they are never disabled.
+    pub fn get(&self, char: Char) -> Option<u8> {
+        match self[char] {
+            value if value == raw::VDISABLE as cc_t && !char.is_count() => None,
+            value => Some(value),
+        }
+    }
+
+    /// Disables `char`, using the `_POSIX_VDISABLE` value of the platform
+    pub fn disable(&mut self, char: Char) {
+        self[char] = raw::VDISABLE as cc_t;
+    }
+
+    /// Checks if `char` is disabled
+    pub fn is_disabled(&self, char: Char) -> bool {
+        self.get(char).is_none()
+    }
+}
+
 impl fmt::Debug for Chars {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
 
         for &char in &CHARS {
-            if is_first {
-                is_first = false;
+            try!(f.write_str(if is_first { "" } else { ", " }));
+            is_first = false;
 
-                try!(write!(f, "{:?}: {:?}", char, self[char]));
+            if char.is_count() {
+                try!(write!(f, "{:?}: {}", char, self[char]));
             } else {
-                try!(write!(f, ", {:?}: {:?}", char, self[char]));
+                try!(write!(f, "{:?}: {}", char, Caret(self.get(char))));
             }
         }
 
@@ -182,6 +645,69 @@ impl fmt::Debug for Chars {
     }
 }
 
+/// Value of a control char in caret notation, as printed and parsed by `stty`
+///
+/// `Caret(None)` is a disabled char (`"undef"`), control characters are printed as `"^C"`,
+/// `DEL` as `"^?"` and bytes with the high bit set get a `"M-"` prefix.
+///
+/// # Examples
+///
+/// ```
+/// use termios::control::Caret;
+///
+/// assert_eq!(Caret(Some(3)).to_string(), "^C");
+/// assert_eq!("^?".parse(), Ok(Caret(Some(127))));
+/// assert_eq!("undef".parse(), Ok(Caret(None)));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Caret(pub Option<u8>);
+
+impl fmt::Display for Caret {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let byte = match self.0 {
+            None => return f.write_str("undef"),
+            Some(byte) => byte,
+        };
+
+        if byte >= 0x80 {
+            try!(f.write_str("M-"));
+        }
+
+        match byte & 0x7f {
+            0x7f => f.write_str("^?"),
+            byte if byte < 0x20 => write!(f, "^{}", (byte + 0x40) as char),
+            byte => write!(f, "{}", byte as char),
+        }
+    }
+}
+
+impl FromStr for Caret {
+    type Err = ParseError;
+
+    /// Parses a value in caret notation, `"undef"` and `"^-"` are disabled chars
+    fn from_str(s: &str) -> Result<Caret, ParseError> {
+        if s == "undef" || s == "^-" {
+            return Ok(Caret(None));
+        }
+
+        let (meta, rest) = if s.starts_with("M-") { (0x80, &s[2..]) } else { (0, s) };
+        let bytes = rest.as_bytes();
+
+        let byte = match bytes.len() {
+            1 if bytes[0] < 0x80 => bytes[0],
+            2 if bytes[0] == b'^' => match bytes[1] {
+                b'?' => 0x7f,
+                byte @ b'@'...b'_' => byte - 0x40,
+                byte @ b'a'...b'z' => byte - 0x60,
+                _ => return Err(ParseError::new("control char value", s)),
+            },
+            _ => return Err(ParseError::new("control char value", s)),
+        };
+
+        Ok(Caret(Some(meta | byte)))
+    }
+}
+
 const CHARS: [Char; 16] = [
     VDISCARD,
     VEOF,
@@ -203,7 +729,7 @@ const CHARS: [Char; 16] = [
 
 /// Standard control chars
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Char {
     VDISCARD,
     VEOF,
@@ -224,6 +750,72 @@ pub enum Char {
 }
 
 impl Char {
+    /// Returns all the control chars, in the order `Debug` prints them
+    pub fn all() -> Vec<Char> {
+        CHARS.to_vec()
+    }
+
+    /// Returns the name of the control char, e.g. `"VEOF"`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            VDISCARD => "VDISCARD",
+            VEOF => "VEOF",
+            VEOL2 => "VEOL2",
+            VEOL => "VEOL",
+            VERASE => "VERASE",
+            VINTR => "VINTR",
+            VKILL => "VKILL",
+            VLNEXT => "VLNEXT",
+            VMIN => "VMIN",
+            VQUIT => "VQUIT",
+            VREPRINT => "VREPRINT",
+            VSTART => "VSTART",
+            VSTOP => "VSTOP",
+            VSUSP => "VSUSP",
+            VTIME => "VTIME",
+            VWERASE => "VWERASE",
+        }
+    }
+
+    /// Returns a short description of the control char
+    pub fn description(&self) -> &'static str {
+        match *self {
+            VDISCARD => "Toggle discarding of pending output",
+            VEOF => "End of file",
+            VEOL2 => "Additional end of line",
+            VEOL => "End of line",
+            VERASE => "Erase the previous character",
+            VINTR => "Send SIGINT",
+            VKILL => "Erase the current line",
+            VLNEXT => "Insert the next character literally",
+            VMIN => "Minimum number of bytes for a non-canonical read",
+            VQUIT => "Send SIGQUIT",
+            VREPRINT => "Reprint the current line",
+            VSTART => "Restart output",
+            VSTOP => "Stop output",
+            VSUSP => "Send SIGTSTP",
+            VTIME => "Timeout, in deciseconds, for a non-canonical read",
+            VWERASE => "Erase the previous word",
+        }
+    }
+
+    /// Checks if the control char is specified by POSIX, otherwise it's an extension
+    pub fn is_posix(&self) -> bool {
+        match *self {
+            VEOF | VEOL | VERASE | VINTR | VKILL | VMIN | VQUIT | VSTART | VSTOP | VSUSP |
+            VTIME => true,
+            _ => false,
+        }
+    }
+
+    // `VMIN` and `VTIME` hold counts rather than characters
+    fn is_count(&self) -> bool {
+        match *self {
+            VMIN | VTIME => true,
+            _ => false,
+        }
+    }
+
     fn to_raw(&self) -> usize {
         (match *self {
             VDISCARD => raw::VDISCARD,
@@ -245,3 +837,17 @@ impl Char {
         }) as usize
     }
 }
+
+impl FromStr for Char {
+    type Err = ParseError;
+
+    /// Parses the name of the control char, case insensitively (e.g. `"veof"`)
+    fn from_str(s: &str) -> Result<Char, ParseError> {
+        let name = s.to_uppercase();
+
+        match Char::all().into_iter().find(|x| x.name() == name) {
+            Some(x) => Ok(x),
+            None => Err(ParseError::new("control char", s)),
+        }
+    }
+}
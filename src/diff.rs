@@ -0,0 +1,428 @@
+//! Structured differences between two `Termios` structures
+
+use std::fmt;
+
+use control::{CSIZE, Caret, Char};
+#[cfg(target_os = "linux")]
+use discipline::LineDiscipline;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use output::{BackspaceDelay, CarriageReturnDelay, FormFeedDelay, NewlineDelay};
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use output::VerticalTabDelay;
+use output::TabDelay;
+use raw::tcflag_t;
+use traits::{Clear, Contains, Get, GetFrom, Set};
+use {Error, Speed, Termios, control, input, local, output};
+
+/// Flags that were added and removed
+#[derive(Clone, Debug, PartialEq)]
+pub struct Changes<T> {
+    /// Flags that are only set in the new `Termios`
+    pub added: Vec<T>,
+    /// Flags that are only set in the old `Termios`
+    pub removed: Vec<T>,
+    /// Bits unknown to this crate that are only set in the new `Termios`
+    pub added_bits: tcflag_t,
+    /// Bits unknown to this crate that are only set in the old `Termios`
+    pub removed_bits: tcflag_t,
+}
+
+impl<T> Changes<T> where T: Copy, Termios: Clear<T> + Contains<T> + Set<T> {
+    // `old_bits` and `new_bits` are the unknown bits of the field
+    fn new(flags: Vec<T>, old: &Termios, new: &Termios, old_bits: tcflag_t, new_bits: tcflag_t)
+           -> Changes<T> {
+        let mut changes = Changes {
+            added: vec![],
+            removed: vec![],
+            added_bits: new_bits & !old_bits,
+            removed_bits: old_bits & !new_bits,
+        };
+
+        for flag in flags {
+            match (old.contains(flag), new.contains(flag)) {
+                (false, true) => changes.added.push(flag),
+                (true, false) => changes.removed.push(flag),
+                _ => {},
+            }
+        }
+
+        changes
+    }
+
+    // `bits` is the raw field
+    fn apply(&self, termios: &mut Termios, bits: fn(&mut Termios) -> &mut tcflag_t) {
+        for &flag in &self.added {
+            termios.set(flag);
+        }
+
+        for &flag in &self.removed {
+            termios.clear(flag);
+        }
+
+        let bits = bits(termios);
+        *bits |= self.added_bits;
+        *bits &= !self.removed_bits;
+    }
+
+    /// Checks if no flag was added or removed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() &&
+            self.removed.is_empty() &&
+            self.added_bits == 0 &&
+            self.removed_bits == 0
+    }
+}
+
+fn iflag(termios: &mut Termios) -> &mut tcflag_t {
+    unsafe { &mut termios.as_raw_mut().c_iflag }
+}
+
+fn oflag(termios: &mut Termios) -> &mut tcflag_t {
+    unsafe { &mut termios.as_raw_mut().c_oflag }
+}
+
+fn cflag(termios: &mut Termios) -> &mut tcflag_t {
+    unsafe { &mut termios.as_raw_mut().c_cflag }
+}
+
+fn lflag(termios: &mut Termios) -> &mut tcflag_t {
+    unsafe { &mut termios.as_raw_mut().c_lflag }
+}
+
+impl<T> fmt::Display for Changes<T> where T: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut is_first = true;
+
+        for flag in &self.added {
+            try!(write!(f, "{}+{:?}", if is_first { "" } else { " " }, flag));
+            is_first = false;
+        }
+
+        for flag in &self.removed {
+            try!(write!(f, "{}-{:?}", if is_first { "" } else { " " }, flag));
+            is_first = false;
+        }
+
+        if self.added_bits != 0 {
+            try!(write!(f, "{}+{:#x}", if is_first { "" } else { " " }, self.added_bits));
+            is_first = false;
+        }
+
+        if self.removed_bits != 0 {
+            try!(write!(f, "{}-{:#x}", if is_first { "" } else { " " }, self.removed_bits));
+        }
+
+        Ok(())
+    }
+}
+
+/// A multi-bit field that changed, with its old and new values
+///
+/// Platform specific fields are only available on the platforms that support them.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+    /// Newline delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    NewlineDelay(NewlineDelay, NewlineDelay),
+    /// Carriage return delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    CarriageReturnDelay(CarriageReturnDelay, CarriageReturnDelay),
+    /// Horizontal tab delay
+    TabDelay(TabDelay, TabDelay),
+    /// Backspace delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    BackspaceDelay(BackspaceDelay, BackspaceDelay),
+    /// Vertical tab delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    VerticalTabDelay(VerticalTabDelay, VerticalTabDelay),
+    /// Form feed delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    FormFeedDelay(FormFeedDelay, FormFeedDelay),
+    /// Line discipline
+    #[cfg(target_os = "linux")]
+    LineDiscipline(LineDiscipline, LineDiscipline),
+}
+
+impl Field {
+    fn apply(&self, termios: &mut Termios) {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::NewlineDelay(_, new) => termios.set(new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::CarriageReturnDelay(_, new) => termios.set(new),
+            Field::TabDelay(_, new) => termios.set(new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::BackspaceDelay(_, new) => termios.set(new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::VerticalTabDelay(_, new) => termios.set(new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::FormFeedDelay(_, new) => termios.set(new),
+            #[cfg(target_os = "linux")]
+            Field::LineDiscipline(_, new) => termios.set(new),
+        }
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::NewlineDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::CarriageReturnDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            Field::TabDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::BackspaceDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::VerticalTabDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            Field::FormFeedDelay(old, new) => write!(f, "oflag:\t{:?} -> {:?}", old, new),
+            #[cfg(target_os = "linux")]
+            Field::LineDiscipline(old, new) => write!(f, "line:\t{:?} -> {:?}", old, new),
+        }
+    }
+}
+
+// Returns the old and new values of `T`, if they differ
+fn change<T>(old: &Termios, new: &Termios) -> Option<(T, T)>
+    where T: GetFrom<Termios> + PartialEq
+{
+    let (old, new) = (old.get::<T>(), new.get::<T>());
+
+    if old == new { None } else { Some((old, new)) }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn delays(old: &Termios, new: &Termios, fields: &mut Vec<Field>) {
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::NewlineDelay(old, new));
+    }
+
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::CarriageReturnDelay(old, new));
+    }
+
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::TabDelay(old, new));
+    }
+
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::BackspaceDelay(old, new));
+    }
+
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::VerticalTabDelay(old, new));
+    }
+
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::FormFeedDelay(old, new));
+    }
+}
+
+#[cfg(target_os = "freebsd")]
+fn delays(old: &Termios, new: &Termios, fields: &mut Vec<Field>) {
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::TabDelay(old, new));
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn line(old: &Termios, new: &Termios, fields: &mut Vec<Field>) {
+    if let Some((old, new)) = change(old, new) {
+        fields.push(Field::LineDiscipline(old, new));
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn line(_: &Termios, _: &Termios, _: &mut Vec<Field>) {}
+
+/// The changes required to turn a `Termios` structure into another one
+///
+/// Returned by `Termios::diff`. The `Display` implementation prints one line per changed field.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diff {
+    /// Changes to the input flags
+    pub input: Changes<input::Flag>,
+    /// Changes to the output flags
+    pub output: Changes<output::Flag>,
+    /// Changes to the control flags
+    pub control: Changes<control::Flag>,
+    /// Changes to the local flags
+    pub local: Changes<local::Flag>,
+    /// Old and new character size
+    pub csize: Option<(CSIZE, CSIZE)>,
+    /// Changes to the output delays and, on Linux, to the line discipline
+    pub fields: Vec<Field>,
+    /// Control chars with their old and new values, `None` is a disabled char
+    pub chars: Vec<(Char, Option<u8>, Option<u8>)>,
+    /// Old and new input speeds
+    pub ispeed: Option<(Speed, Speed)>,
+    /// Old and new output speeds
+    pub ospeed: Option<(Speed, Speed)>,
+}
+
+impl Diff {
+    /// Checks if there are no changes
+    pub fn is_empty(&self) -> bool {
+        self.input.is_empty() &&
+            self.output.is_empty() &&
+            self.control.is_empty() &&
+            self.local.is_empty() &&
+            self.csize.is_none() &&
+            self.fields.is_empty() &&
+            self.chars.is_empty() &&
+            self.ispeed.is_none() &&
+            self.ospeed.is_none()
+    }
+
+    /// Applies the changes to `termios`, as a patch
+    ///
+    /// Only the changed fields are touched, the rest of `termios` is left as it is.
+    pub fn apply(&self, termios: &mut Termios) {
+        self.input.apply(termios, iflag);
+        self.output.apply(termios, oflag);
+        self.control.apply(termios, cflag);
+        self.local.apply(termios, lflag);
+
+        if let Some((_, csize)) = self.csize {
+            termios.set(csize);
+        }
+
+        for field in &self.fields {
+            field.apply(termios);
+        }
+
+        for &(char, _, value) in &self.chars {
+            match value {
+                Some(value) => termios.cc[char] = value,
+                None => termios.cc.disable(char),
+            }
+        }
+
+        if let Some((_, speed)) = self.ispeed {
+            termios.set_ispeed(speed);
+        }
+
+        if let Some((_, speed)) = self.ospeed {
+            termios.set_ospeed(speed);
+        }
+    }
+}
+
+impl fmt::Display for Diff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.input.is_empty() {
+            try!(writeln!(f, "iflag:\t{}", self.input));
+        }
+
+        if !self.output.is_empty() {
+            try!(writeln!(f, "oflag:\t{}", self.output));
+        }
+
+        if !self.control.is_empty() {
+            try!(writeln!(f, "cflag:\t{}", self.control));
+        }
+
+        if let Some((old, new)) = self.csize {
+            try!(writeln!(f, "csize:\t{:?} -> {:?}", old, new));
+        }
+
+        if !self.local.is_empty() {
+            try!(writeln!(f, "lflag:\t{}", self.local));
+        }
+
+        for field in &self.fields {
+            try!(writeln!(f, "{}", field));
+        }
+
+        for &(char, old, new) in &self.chars {
+            try!(writeln!(f, "cc:\t{:?}: {} -> {}", char, Caret(old), Caret(new)));
+        }
+
+        if let Some((old, new)) = self.ispeed {
+            try!(writeln!(f, "ispeed:\t{:?} -> {:?}", old, new));
+        }
+
+        if let Some((old, new)) = self.ospeed {
+            try!(writeln!(f, "ospeed:\t{:?} -> {:?}", old, new));
+        }
+
+        Ok(())
+    }
+}
+
+impl Termios {
+    /// Returns the changes required to turn `self` into `other`
+    ///
+    /// # Examples
+    ///
+    /// ``` no_run
+    /// #![feature(libc)]
+    ///
+    /// extern crate libc;
+    /// extern crate termios;
+    ///
+    /// use termios::prelude::*;
+    ///
+    /// fn main() {
+    ///     let cooked = Termios::fetch(libc::STDIN_FILENO).unwrap();
+    ///     let mut raw = cooked;
+    ///     raw.make_raw();
+    ///     print!("{}", cooked.diff(&raw));
+    /// }
+    /// ```
+    ///
+    /// ``` text
+    /// iflag:  -ICRNL -IXON
+    /// oflag:  -OPOST
+    /// lflag:  -ECHO -ICANON -IEXTEN -ISIG
+    /// ```
+    pub fn diff(&self, other: &Termios) -> Diff {
+        let (old_ispeed, new_ispeed) = (self.ispeed(), other.ispeed());
+        let (old_ospeed, new_ospeed) = (self.ospeed(), other.ospeed());
+        let mut fields = vec![];
+
+        delays(self, other, &mut fields);
+        line(self, other, &mut fields);
+
+        Diff {
+            input: Changes::new(input::Flag::all(),
+                                self,
+                                other,
+                                self.iflag.unknown_bits(),
+                                other.iflag.unknown_bits()),
+            output: Changes::new(output::Flag::all(),
+                                 self,
+                                 other,
+                                 self.oflag.unknown_bits(),
+                                 other.oflag.unknown_bits()),
+            control: Changes::new(control::Flag::all(),
+                                  self,
+                                  other,
+                                  self.cflag.unknown_bits(),
+                                  other.cflag.unknown_bits()),
+            local: Changes::new(local::Flag::all(),
+                                self,
+                                other,
+                                self.lflag.unknown_bits(),
+                                other.lflag.unknown_bits()),
+            csize: change::<CSIZE>(self, other),
+            fields: fields,
+            chars: Char::all().into_iter().filter_map(|char| {
+                let (old, new) = (self.cc.get(char), other.cc.get(char));
+
+                if old == new { None } else { Some((char, old, new)) }
+            }).collect(),
+            ispeed: changed(old_ispeed, new_ispeed),
+            ospeed: changed(old_ospeed, new_ospeed),
+        }
+    }
+}
+
+// NB Speeds unknown to this crate can't be represented, they are left out of the diff
+fn changed(old: Result<Speed, Error>, new: Result<Speed, Error>) -> Option<(Speed, Speed)> {
+    match (old, new) {
+        (Ok(old), Ok(new)) if old != new => Some((old, new)),
+        _ => None,
+    }
+}
@@ -0,0 +1,208 @@
+//! Line disciplines (Linux only)
+//!
+//! The line discipline sits between the TTY driver and the process. It implements the behavior
+//! described by the `Termios` structure (`N_TTY`), or turns the serial line into a network
+//! interface (`N_SLIP`, `N_PPP`), a multiplexer (`N_GSM0710`), etc.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! extern crate termios;
+//!
+//! use std::fs::OpenOptions;
+//!
+//! use termios::discipline::LineDiscipline;
+//! use termios::prelude::*;
+//!
+//! fn main() {
+//!     let modem = OpenOptions::new().read(true).write(true).open("/dev/ttyUSB0").unwrap();
+//!
+//!     modem.set_line_discipline(LineDiscipline::Gsm0710).unwrap();
+//! }
+//! ```
+
+use libc::{c_int, c_ulong};
+
+use {Error, Termios};
+use raw::{cc_t, self};
+use traits::{GetFrom, Set};
+
+const FAILURE: c_int = -1;
+const SUCCESS: c_int = 0;
+
+/// Line discipline
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LineDiscipline {
+    /// `N_TTY`, the default discipline, implements the `Termios` structure
+    Tty,
+    /// `N_SLIP`, Serial Line IP
+    Slip,
+    /// `N_MOUSE`, serial mouse
+    Mouse,
+    /// `N_PPP`, Point-to-Point Protocol
+    Ppp,
+    /// `N_STRIP`, Starmode Radio IP
+    Strip,
+    /// `N_AX25`, amateur radio AX.25
+    Ax25,
+    /// `N_X25`, X.25 async
+    X25,
+    /// `N_6PACK`, amateur radio 6pack protocol
+    SixPack,
+    /// `N_MASC`, Mobitex module
+    Masc,
+    /// `N_R3964`, Simatic R3964 module
+    R3964,
+    /// `N_PROFIBUS_FDL`, Profibus
+    ProfibusFdl,
+    /// `N_IRDA`, Linux IrDA
+    Irda,
+    /// `N_SMSBLOCK`, SMS block mode, to talk to GSM data cards
+    SmsBlock,
+    /// `N_HDLC`, synchronous HDLC
+    Hdlc,
+    /// `N_SYNC_PPP`, synchronous PPP
+    SyncPpp,
+    /// `N_HCI`, Bluetooth HCI UART
+    Hci,
+    /// `N_GIGASET_M101`, Siemens Gigaset M101 serial DECT adapter
+    GigasetM101,
+    /// `N_SLCAN`, serial/USB serial CAN adapters
+    Slcan,
+    /// `N_PPS`, Pulse Per Second
+    Pps,
+    /// `N_V253`, codec control over voice modem
+    V253,
+    /// `N_CAIF`, CAIF protocol, to talk to modems
+    Caif,
+    /// `N_GSM0710`, GSM 07.10 multiplexer
+    Gsm0710,
+    /// `N_TI_WL`, TI's WiLink Bluetooth, FM and GPS combo chips
+    TiWl,
+    /// `N_TRACESINK`, trace data routing for MIPI P1149.7
+    TraceSink,
+    /// `N_TRACEROUTER`, trace data routing for MIPI P1149.7
+    TraceRouter,
+    /// A discipline unknown to this crate
+    Other(u8),
+}
+
+impl LineDiscipline {
+    fn from_raw(line: cc_t) -> LineDiscipline {
+        use self::LineDiscipline::*;
+
+        match line as c_int {
+            raw::N_TTY => Tty,
+            raw::N_SLIP => Slip,
+            raw::N_MOUSE => Mouse,
+            raw::N_PPP => Ppp,
+            raw::N_STRIP => Strip,
+            raw::N_AX25 => Ax25,
+            raw::N_X25 => X25,
+            raw::N_6PACK => SixPack,
+            raw::N_MASC => Masc,
+            raw::N_R3964 => R3964,
+            raw::N_PROFIBUS_FDL => ProfibusFdl,
+            raw::N_IRDA => Irda,
+            raw::N_SMSBLOCK => SmsBlock,
+            raw::N_HDLC => Hdlc,
+            raw::N_SYNC_PPP => SyncPpp,
+            raw::N_HCI => Hci,
+            raw::N_GIGASET_M101 => GigasetM101,
+            raw::N_SLCAN => Slcan,
+            raw::N_PPS => Pps,
+            raw::N_V253 => V253,
+            raw::N_CAIF => Caif,
+            raw::N_GSM0710 => Gsm0710,
+            raw::N_TI_WL => TiWl,
+            raw::N_TRACESINK => TraceSink,
+            raw::N_TRACEROUTER => TraceRouter,
+            _ => Other(line),
+        }
+    }
+
+    fn to_raw(&self) -> cc_t {
+        use self::LineDiscipline::*;
+
+        (match *self {
+            Tty => raw::N_TTY,
+            Slip => raw::N_SLIP,
+            Mouse => raw::N_MOUSE,
+            Ppp => raw::N_PPP,
+            Strip => raw::N_STRIP,
+            Ax25 => raw::N_AX25,
+            X25 => raw::N_X25,
+            SixPack => raw::N_6PACK,
+            Masc => raw::N_MASC,
+            R3964 => raw::N_R3964,
+            ProfibusFdl => raw::N_PROFIBUS_FDL,
+            Irda => raw::N_IRDA,
+            SmsBlock => raw::N_SMSBLOCK,
+            Hdlc => raw::N_HDLC,
+            SyncPpp => raw::N_SYNC_PPP,
+            Hci => raw::N_HCI,
+            GigasetM101 => raw::N_GIGASET_M101,
+            Slcan => raw::N_SLCAN,
+            Pps => raw::N_PPS,
+            V253 => raw::N_V253,
+            Caif => raw::N_CAIF,
+            Gsm0710 => raw::N_GSM0710,
+            TiWl => raw::N_TI_WL,
+            TraceSink => raw::N_TRACESINK,
+            TraceRouter => raw::N_TRACEROUTER,
+            Other(line) => return line,
+        }) as cc_t
+    }
+}
+
+/// Returns the line discipline currently attached to `fd` (`TIOCGETD`)
+pub fn fetch(fd: c_int) -> Result<LineDiscipline, Error> {
+    unsafe {
+        let mut line: c_int = 0;
+
+        match raw::ioctl(fd, raw::TIOCGETD as c_ulong, &mut line as *mut c_int) {
+            FAILURE => Err(Error::last_os_error()),
+            SUCCESS => Ok(LineDiscipline::from_raw(line as cc_t)),
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// Attaches the line discipline `line` to `fd` (`TIOCSETD`)
+///
+/// Attaching a discipline other than `LineDiscipline::Tty` may require privileges, or the kernel
+/// module that implements it.
+pub fn update(fd: c_int, line: LineDiscipline) -> Result<(), Error> {
+    let line = line.to_raw() as c_int;
+
+    unsafe {
+        match raw::ioctl(fd, raw::TIOCSETD as c_ulong, &line as *const c_int) {
+            FAILURE => Err(Error::last_os_error()),
+            SUCCESS => Ok(()),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Termios {
+    /// Returns the line discipline that was attached when the `Termios` structure was fetched
+    ///
+    /// The kernel ignores this field on `update`, use `discipline::update` to change the
+    /// discipline.
+    pub fn line_discipline(&self) -> LineDiscipline {
+        LineDiscipline::from_raw(self._line)
+    }
+}
+
+impl GetFrom<Termios> for LineDiscipline {
+    fn get_from(termios: &Termios) -> LineDiscipline {
+        termios.line_discipline()
+    }
+}
+
+// NB Only changes the field, the kernel ignores it on `update`
+impl Set<LineDiscipline> for Termios {
+    fn set(&mut self, line: LineDiscipline) {
+        self._line = line.to_raw();
+    }
+}
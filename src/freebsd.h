@@ -1,5 +1,8 @@
+#include <signal.h>
 #include <stdint.h>
+#include <stdlib.h>
 #include <termios.h>
+#include <unistd.h>
 
 // Special control characters
 enum {
@@ -160,3 +163,34 @@ enum {
     EXTA_ = EXTA,
     EXTB_ = EXTB,
 };
+
+// Signals
+enum {
+    __SIGNAL = (int)(-1),
+    SIGHUP_ = SIGHUP,
+    SIGINT_ = SIGINT,
+    SIGQUIT_ = SIGQUIT,
+    SIGTERM_ = SIGTERM,
+    SIGTSTP_ = SIGTSTP,
+    SIGTTOU_ = SIGTTOU,
+};
+
+// sigprocmask
+enum {
+    __SIGPROCMASK = (int)(-1),
+    SIG_BLOCK_ = SIG_BLOCK,
+    SIG_SETMASK_ = SIG_SETMASK,
+    SIG_UNBLOCK_ = SIG_UNBLOCK,
+};
+
+// Signal dispositions
+enum {
+    __SIGHANDLER = (uintptr_t)(-1),
+    SIG_IGN_ = (uintptr_t)SIG_IGN,
+};
+
+// Disabled control character
+enum {
+    __VDISABLE = (int)(-1),
+    _POSIX_VDISABLE_ = _POSIX_VDISABLE,
+};
@@ -0,0 +1,106 @@
+//! Scoped changes to the `Termios` of a file descriptor
+
+use libc::c_int;
+use std::io::{Write, self};
+use std::mem;
+
+use {Error, Termios, When};
+
+impl Termios {
+    /// Modifies the `Termios` structure associated with the `fd` (file descriptor), the original
+    /// structure is restored when the returned guard goes out of scope
+    ///
+    /// # Examples
+    ///
+    /// Disable echo, even if the loop panics, echo will be re-enabled.
+    ///
+    /// ``` no_run
+    /// #![feature(libc)]
+    ///
+    /// extern crate libc;
+    /// extern crate termios;
+    ///
+    /// use std::io::{Read, self};
+    ///
+    /// use termios::prelude::*;
+    ///
+    /// fn main() {
+    ///     let guard = Termios::modify(libc::STDIN_FILENO, |termios| {
+    ///         termios.clear(local::Flag::ICANON);
+    ///         termios.clear(local::Flag::ECHO);
+    ///     }).unwrap();
+    ///
+    ///     let stdin = io::stdin();
+    ///
+    ///     for byte in stdin.lock().bytes() {
+    ///         println!("Got {}", byte.unwrap());
+    ///     }
+    ///
+    ///     // Or let `guard` go out of scope
+    ///     guard.restore().unwrap();
+    /// }
+    /// ```
+    pub fn modify<F>(fd: c_int, f: F) -> Result<TermiosGuard, Error>
+        where F: FnOnce(&mut Termios)
+    {
+        let saved = try!(Termios::fetch(fd));
+        let mut termios = saved;
+
+        f(&mut termios);
+
+        try!(termios.update(fd, When::Now));
+
+        Ok(TermiosGuard {
+            fd: fd,
+            saved: saved,
+        })
+    }
+}
+
+/// Restores the saved `Termios` structure of a file descriptor when dropped
+///
+/// Created by `Termios::modify`. If the restoration fails during `drop`, the error is reported
+/// to stderr, use `restore` to handle the error yourself.
+#[must_use]
+pub struct TermiosGuard {
+    fd: c_int,
+    saved: Termios,
+}
+
+impl TermiosGuard {
+    /// Returns the file descriptor that will be restored
+    pub fn fd(&self) -> c_int {
+        self.fd
+    }
+
+    /// Returns the `Termios` structure that will be restored
+    pub fn saved(&self) -> &Termios {
+        &self.saved
+    }
+
+    /// Restores the saved `Termios` structure now, reporting any error
+    pub fn restore(self) -> Result<(), Error> {
+        let result = self.saved.update(self.fd, When::Now);
+
+        mem::forget(self);
+
+        result
+    }
+
+    /// Disarms the guard, the saved `Termios` structure is returned but not restored
+    pub fn forget(self) -> Termios {
+        let saved = self.saved;
+
+        mem::forget(self);
+
+        saved
+    }
+}
+
+impl Drop for TermiosGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.saved.update(self.fd, When::Now) {
+            let _ = writeln!(io::stderr(), "termios: failed to restore fd {}: {}", self.fd, e);
+        }
+    }
+}
@@ -1,22 +1,25 @@
 //! Software input processing
 
 use std::fmt;
+use std::str::FromStr;
 
-use Termios;
+use {ParseError, Termios};
 use raw::{tcflag_t, self};
 use self::Flag::*;
-use traits::{Clear, Contains, Set};
+use traits::{Clear, Contains, GetFrom, Set};
 
 /// Input flags
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 #[repr(C)]
 pub struct Flags(tcflag_t);
 
+flags!(0);
+
 impl fmt::Debug for Flags {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
 
-        for &flag in &FLAGS {
+        for flag in Flag::all() {
             let value = flag.to_raw();
 
             if self.0 & value == value {
@@ -30,6 +33,16 @@ impl fmt::Debug for Flags {
             }
         }
 
+        let unknown = self.unknown_bits();
+
+        if unknown != 0 {
+            if is_first {
+                try!(write!(f, "{:#x}", unknown));
+            } else {
+                try!(write!(f, " | {:#x}", unknown));
+            }
+        }
+
         Ok(())
     }
 }
@@ -50,8 +63,25 @@ const FLAGS: [Flag; 13] = [
     PARMRK,
 ];
 
+#[cfg(target_os = "freebsd")]
+const EXTENSIONS: [Flag; 0] = [];
+
+#[cfg(target_os = "linux")]
+const EXTENSIONS: [Flag; 2] = [
+    IUCLC,
+    IUTF8,
+];
+
+#[cfg(target_os = "macos")]
+const EXTENSIONS: [Flag; 1] = [
+    IUTF8,
+];
+
 /// Standard input flags
-#[derive(Clone, Copy, Debug)]
+///
+/// Platform specific flags are only available on the platforms that support them, use
+/// `Flag::all()` to list the flags supported by the current target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Flag {
     /// Map BREAK to SIGINTR
     BRKINT,
@@ -71,6 +101,12 @@ pub enum Flag {
     INPCK,
     /// Strip 8th bit off chars
     ISTRIP,
+    /// Map upper case to lower case on input
+    #[cfg(target_os = "linux")]
+    IUCLC,
+    /// Input is UTF-8, allows character erase to work in cooked mode
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    IUTF8,
     /// Any char will restart after stop
     IXANY,
     /// Enable input control flow
@@ -82,6 +118,66 @@ pub enum Flag {
 }
 
 impl Flag {
+    /// Returns all the flags supported by the current target, in the order `Debug` prints them
+    pub fn all() -> Vec<Flag> {
+        FLAGS.iter().chain(EXTENSIONS.iter()).cloned().collect()
+    }
+
+    /// Returns the name of the flag, e.g. `"ICRNL"`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            BRKINT => "BRKINT",
+            ICRNL => "ICRNL",
+            IGNBRK => "IGNBRK",
+            IGNCR => "IGNCR",
+            IGNPAR => "IGNPAR",
+            IMAXBEL => "IMAXBEL",
+            INLCR => "INLCR",
+            INPCK => "INPCK",
+            ISTRIP => "ISTRIP",
+            #[cfg(target_os = "linux")]
+            IUCLC => "IUCLC",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            IUTF8 => "IUTF8",
+            IXANY => "IXANY",
+            IXOFF => "IXOFF",
+            IXON => "IXON",
+            PARMRK => "PARMRK",
+        }
+    }
+
+    /// Returns a short description of the flag
+    pub fn description(&self) -> &'static str {
+        match *self {
+            BRKINT => "Map BREAK to SIGINTR",
+            ICRNL => "Map CR to NL (ala CRMOD)",
+            IGNBRK => "Ignore BREAK condition",
+            IGNCR => "Ignore CR",
+            IGNPAR => "Ignore (discard) parity errors",
+            IMAXBEL => "Ring bell on input queue full",
+            INLCR => "Map NL into CR",
+            INPCK => "Enable checking of parity errors",
+            ISTRIP => "Strip 8th bit off chars",
+            #[cfg(target_os = "linux")]
+            IUCLC => "Map upper case to lower case on input",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            IUTF8 => "Input is UTF-8, allows character erase to work in cooked mode",
+            IXANY => "Any char will restart after stop",
+            IXOFF => "Enable input control flow",
+            IXON => "Enable output flow control",
+            PARMRK => "Mark parity and framing errors",
+        }
+    }
+
+    /// Checks if the flag is specified by POSIX, otherwise it's an extension
+    pub fn is_posix(&self) -> bool {
+        match *self {
+            BRKINT | ICRNL | IGNBRK | IGNCR | IGNPAR | INLCR | INPCK | ISTRIP | IXANY | IXOFF |
+            IXON | PARMRK => true,
+            _ => false,
+        }
+    }
+
     fn to_raw(&self) -> tcflag_t {
         match *self {
             BRKINT => raw::BRKINT,
@@ -93,6 +189,10 @@ impl Flag {
             INLCR => raw::INLCR,
             INPCK => raw::INPCK,
             ISTRIP => raw::ISTRIP,
+            #[cfg(target_os = "linux")]
+            IUCLC => raw::IUCLC,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            IUTF8 => raw::IUTF8,
             IXANY => raw::IXANY,
             IXOFF => raw::IXOFF,
             IXON => raw::IXON,
@@ -101,6 +201,20 @@ impl Flag {
     }
 }
 
+impl FromStr for Flag {
+    type Err = ParseError;
+
+    /// Parses the name of the flag, case insensitively (e.g. `"icrnl"`)
+    fn from_str(s: &str) -> Result<Flag, ParseError> {
+        let name = s.to_uppercase();
+
+        match Flag::all().into_iter().find(|x| x.name() == name) {
+            Some(x) => Ok(x),
+            None => Err(ParseError::new("input flag", s)),
+        }
+    }
+}
+
 impl Clear<Flag> for Termios {
     fn clear(&mut self, flag: Flag) {
         self.iflag.0 &= !flag.to_raw()
@@ -120,3 +234,45 @@ impl Set<Flag> for Termios {
         self.iflag.0 |= flag.to_raw()
     }
 }
+
+/// How received line endings are translated, a typed view of `ICRNL`, `IGNCR` and `INLCR`
+///
+/// `Lf` and `Cr` are named after the line ending the device sends, which the process reads as
+/// `\n`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum InputNewline {
+    /// LF, no translation
+    Lf,
+    /// CR, translated into LF (`ICRNL`). This is what a keyboard's Enter key sends
+    Cr,
+    /// The carriage returns are ignored (`IGNCR`), for devices that send CR LF
+    Ignore,
+    /// A combination of the above flags that doesn't describe a line ending, like `ICRNL` together
+    /// with `INLCR` (which swaps CR and LF) or `INLCR` on its own (which turns LF into CR)
+    Conflicting(Flags),
+}
+
+const NEWLINE_MASK: tcflag_t = raw::ICRNL | raw::IGNCR | raw::INLCR;
+
+impl GetFrom<Termios> for InputNewline {
+    fn get_from(termios: &Termios) -> InputNewline {
+        match termios.iflag.0 & NEWLINE_MASK {
+            0 => InputNewline::Lf,
+            raw::ICRNL => InputNewline::Cr,
+            raw::IGNCR => InputNewline::Ignore,
+            flags => InputNewline::Conflicting(Flags(flags)),
+        }
+    }
+}
+
+impl Set<InputNewline> for Termios {
+    fn set(&mut self, newline: InputNewline) {
+        self.iflag.0 &= !NEWLINE_MASK;
+        self.iflag.0 |= match newline {
+            InputNewline::Lf => 0,
+            InputNewline::Cr => raw::ICRNL,
+            InputNewline::Ignore => raw::IGNCR,
+            InputNewline::Conflicting(flags) => flags.0 & NEWLINE_MASK,
+        };
+    }
+}
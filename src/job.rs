@@ -0,0 +1,199 @@
+//! Job control
+
+use libc::c_int;
+use std::process::{Command, ExitStatus};
+use std::{io, mem, ptr};
+
+use {Error, Termios, When};
+use raw;
+
+/// Suspends the process, like pressing Ctrl+Z does when `local::Flag::ISIG` is set
+///
+/// Applications that clear `ISIG` and handle Ctrl+Z themselves should call this function. The
+/// current `Termios` structure of `fd` is saved, `saved` (e.g. the `Termios` structure fetched
+/// at startup) is restored and the process is stopped by raising `SIGTSTP`. Once the process is
+/// continued (`SIGCONT`) and back in the foreground, the application's `Termios` structure is
+/// reapplied and `redraw` is called to let the application repaint the screen.
+///
+/// # Examples
+///
+/// ``` no_run
+/// #![feature(libc)]
+///
+/// extern crate libc;
+/// extern crate termios;
+///
+/// use std::io::{Read, self};
+///
+/// use termios::job;
+/// use termios::prelude::*;
+///
+/// // a.k.a. "Ctrl+Z"
+/// const SUBSTITUTE: u8 = 26;
+///
+/// fn main() {
+///     let cooked = Termios::fetch(libc::STDIN_FILENO).unwrap();
+///     let mut raw = cooked;
+///     raw.make_raw();
+///     raw.update(libc::STDIN_FILENO, When::Now).unwrap();
+///
+///     let stdin = io::stdin();
+///
+///     for byte in stdin.lock().bytes() {
+///         match byte.unwrap() {
+///             SUBSTITUTE => {
+///                 job::suspend(libc::STDIN_FILENO, &cooked, || print!("\r[resumed]")).unwrap()
+///             },
+///             b'q' => break,
+///             _ => {},
+///         }
+///     }
+///
+///     cooked.update(libc::STDIN_FILENO, When::Now).unwrap();
+/// }
+/// ```
+pub fn suspend<F>(fd: c_int, saved: &Termios, redraw: F) -> Result<(), Error>
+    where F: FnOnce()
+{
+    let active = try!(Termios::fetch(fd));
+
+    try!(saved.update(fd, When::AfterDrain));
+
+    unsafe {
+        // Make sure the default action (stop) is performed, even if the application installed a
+        // handler for `SIGTSTP`
+        let previous = raw::signal(raw::SIGTSTP, None);
+        raw::raise(raw::SIGTSTP);
+        raw::signal(raw::SIGTSTP, previous);
+    }
+
+    // NB If the process was continued in the background, this stops it (`SIGTTOU`) until it's
+    // brought back to the foreground
+    try!(active.update(fd, When::Now));
+
+    redraw();
+
+    Ok(())
+}
+
+/// Hands the terminal over to a subprocess and takes it back once the subprocess exits
+///
+/// `saved` (e.g. the `Termios` structure fetched at startup) is applied to `fd`, `command` is
+/// run and waited for, then `active` is reapplied and any pending input is discarded
+/// (`When::AfterFlush`). `active` is reapplied even if `command` couldn't be spawned, the first
+/// error is reported.
+///
+/// # Examples
+///
+/// ``` no_run
+/// #![feature(libc)]
+///
+/// extern crate libc;
+/// extern crate termios;
+///
+/// use std::process::Command;
+///
+/// use termios::job;
+/// use termios::prelude::*;
+///
+/// fn main() {
+///     let cooked = Termios::fetch(libc::STDIN_FILENO).unwrap();
+///     let mut raw = cooked;
+///     raw.make_raw();
+///     raw.update(libc::STDIN_FILENO, When::Now).unwrap();
+///
+///     let mut editor = Command::new("vi");
+///     editor.arg("notes.txt");
+///     let status = job::run(&mut editor, libc::STDIN_FILENO, &cooked, &raw).unwrap();
+///
+///     cooked.update(libc::STDIN_FILENO, When::Now).unwrap();
+///     println!("editor exited with {}", status);
+/// }
+/// ```
+pub fn run(command: &mut Command, fd: c_int, saved: &Termios, active: &Termios)
+           -> Result<ExitStatus, Error> {
+    try!(saved.update(fd, When::AfterDrain));
+
+    let status = command.status().map_err(Error::from);
+    let restored = active.update(fd, When::AfterFlush);
+
+    let status = try!(status);
+    try!(restored);
+
+    Ok(status)
+}
+
+/// What `Termios::update_background` does when the process is not in the foreground
+pub enum Background {
+    /// Don't update the terminal, return an `Error::NotInForeground` error instead
+    Fail,
+    /// Block `SIGTTOU` during the update
+    Block,
+    /// Ignore `SIGTTOU` during the update
+    Ignore,
+}
+
+/// Checks if the process is in the foreground process group of the terminal associated with
+/// `fd`
+pub fn is_foreground(fd: c_int) -> Result<bool, Error> {
+    unsafe {
+        match raw::tcgetpgrp(fd) {
+            -1 => Err(Error::last_os_error()),
+            pgrp => Ok(pgrp == raw::getpgrp()),
+        }
+    }
+}
+
+impl Termios {
+    /// Like `update`, but safe to call from a background process group
+    ///
+    /// When a process that is not in the foreground process group of the terminal changes its
+    /// `Termios` structure, the process is sent `SIGTTOU`, whose default action silently stops
+    /// the process. `background` selects how to avoid that. Interrupted updates (`EINTR`) are
+    /// retried.
+    pub fn update_background(&self, fd: c_int, when: When, background: Background)
+                             -> Result<(), Error> {
+        match background {
+            Background::Fail => {
+                if try!(is_foreground(fd)) {
+                    self.update_retrying(fd, when)
+                } else {
+                    Err(Error::NotInForeground)
+                }
+            },
+            Background::Block => unsafe {
+                let mut set: raw::sigset_t = mem::zeroed();
+                let mut old: raw::sigset_t = mem::zeroed();
+
+                raw::sigemptyset(&mut set);
+                raw::sigaddset(&mut set, raw::SIGTTOU);
+                raw::pthread_sigmask(raw::SIG_BLOCK, &set, &mut old);
+
+                let result = self.update_retrying(fd, when);
+
+                raw::pthread_sigmask(raw::SIG_SETMASK, &old, ptr::null_mut());
+
+                result
+            },
+            Background::Ignore => unsafe {
+                // NB `SIG_IGN` is a sentinel address, not a function
+                let previous = raw::signal(raw::SIGTTOU, mem::transmute(raw::SIG_IGN as usize));
+
+                let result = self.update_retrying(fd, when);
+
+                raw::signal(raw::SIGTTOU, previous);
+
+                result
+            },
+        }
+    }
+
+    fn update_retrying(&self, fd: c_int, when: When) -> Result<(), Error> {
+        loop {
+            match self.update(fd, when) {
+                Err(Error::Os(ref e)) if e.kind() == io::ErrorKind::Interrupted => {},
+                result => return result,
+            }
+        }
+    }
+}
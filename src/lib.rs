@@ -16,25 +16,136 @@
 extern crate libc;
 
 use libc::c_int;
+#[cfg(target_os = "linux")]
+use libc::c_ulong;
 use std::default::Default;
-use std::{fmt, io, mem};
+use std::str::FromStr;
+use std::{error, fmt, io, mem};
 
 use self::BaudRate::*;
+use self::FlowAction::*;
+use self::Queue::*;
 use self::When::*;
 
+#[macro_use]
+mod macros;
+
 pub mod control;
+pub mod diff;
+#[cfg(target_os = "linux")]
+pub mod discipline;
+pub mod guard;
 pub mod input;
+pub mod job;
 pub mod local;
+pub mod mode;
 pub mod output;
 pub mod prelude;
 pub mod raw;
+pub mod restore;
+pub mod stack;
+pub mod terminal;
 pub mod traits;
+pub mod verify;
 
 const FAILURE: c_int = -1;
 const SUCCESS: c_int = 0;
 
+// `tcgetattr`, except that on Linux custom (`BOTHER`) speeds are fetched with `TCGETS2`
+#[cfg(not(target_os = "linux"))]
+unsafe fn get_attr(fd: c_int, termios: &mut raw::Termios) -> c_int {
+    raw::tcgetattr(fd, termios)
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn get_attr(fd: c_int, termios: &mut raw::Termios) -> c_int {
+    let mut termios2: raw::Termios2 = mem::zeroed();
+
+    match raw::tcgetattr(fd, termios) {
+        SUCCESS if has_custom_speed(termios) => {
+            match raw::ioctl(fd, raw::TCGETS2 as c_ulong, &mut termios2 as *mut raw::Termios2) {
+                SUCCESS => {
+                    termios.c_ispeed = termios2.c_ispeed;
+                    termios.c_ospeed = termios2.c_ospeed;
+                    SUCCESS
+                },
+                result => result,
+            }
+        },
+        result => result,
+    }
+}
+
+// `tcsetattr`, except that on Linux custom (`BOTHER`) speeds are applied with `TCSETS2`
+//
+// NB This function is async-signal-safe, `restore` relies on it
+#[cfg(not(target_os = "linux"))]
+unsafe fn set_attr(fd: c_int, when: c_int, termios: &raw::Termios) -> c_int {
+    raw::tcsetattr(fd, when, termios)
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn set_attr(fd: c_int, when: c_int, termios: &raw::Termios) -> c_int {
+    if !has_custom_speed(termios) {
+        return raw::tcsetattr(fd, when, termios);
+    }
+
+    let request = match when {
+        raw::TCSANOW => raw::TCSETS2,
+        raw::TCSADRAIN => raw::TCSETSW2,
+        raw::TCSAFLUSH => raw::TCSETSF2,
+        _ => unreachable!(),
+    };
+
+    let mut termios2: raw::Termios2 = mem::zeroed();
+    termios2.c_iflag = termios.c_iflag;
+    termios2.c_oflag = termios.c_oflag;
+    termios2.c_cflag = termios.c_cflag;
+    termios2.c_lflag = termios.c_lflag;
+    termios2.c_line = termios.c_line;
+    // NB glibc's `NCCS` is bigger than the kernel's, but the indices of the chars agree
+    for (dst, src) in termios2.c_cc.iter_mut().zip(termios.c_cc.iter()) {
+        *dst = *src;
+    }
+    termios2.c_ispeed = termios.c_ispeed;
+    termios2.c_ospeed = termios.c_ospeed;
+
+    raw::ioctl(fd, request as c_ulong, &termios2 as *const raw::Termios2)
+}
+
+#[cfg(target_os = "linux")]
+fn has_custom_speed(termios: &raw::Termios) -> bool {
+    termios.c_cflag & raw::CBAUD == raw::BOTHER ||
+        (termios.c_cflag >> raw::IBSHIFT) & raw::CBAUD == raw::BOTHER
+}
+
+// `speed` is a `CBAUD` value, `bps` is the matching `c_ispeed`/`c_ospeed` field
+#[cfg(target_os = "linux")]
+fn speed_from_raw(speed: raw::speed_t, bps: raw::speed_t) -> Result<Speed, Error> {
+    if speed == raw::BOTHER {
+        Ok(Speed::from_bits_per_second(bps))
+    } else {
+        match BaudRate::from_raw(speed) {
+            Some(rate) => Ok(Speed::Standard(rate)),
+            None => Err(Error::UnknownBaudRate(speed)),
+        }
+    }
+}
+
+// NB `cfset*speed` can only fail if `termios` is null (impossible in safe code) or if `speed` is
+// an invalid value (that would be a bug)
+unsafe fn cfset(f: unsafe extern "C" fn(*mut raw::Termios, raw::speed_t) -> c_int,
+                termios: &mut raw::Termios,
+                speed: raw::speed_t) {
+    match f(termios, speed) {
+        FAILURE => unreachable!(),
+        SUCCESS => {},
+        _ => unreachable!(),
+    }
+}
+
 /// Safe wrapper around `raw::Termios`
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, PartialEq)]
 #[repr(C)]
 pub struct Termios {
     /// Input flags
@@ -61,15 +172,12 @@ impl Termios {
     ///
     /// ``` no_run
     /// // examples/stdin.rs
-    /// #![feature(libc)]
-    ///
-    /// extern crate libc;
     /// extern crate termios;
     ///
     /// use termios::prelude::*;
     ///
     /// fn main() {
-    ///     println!("{:?}", Termios::fetch(libc::STDIN_FILENO).unwrap());
+    ///     println!("{:?}", Termios::stdin().unwrap());
     /// }
     /// ```
     ///
@@ -79,12 +187,13 @@ impl Termios {
     /// oflag:  ONLCR | OPOST
     /// cflag:  CS8 | CREAD
     /// lflag:  ECHOCTL | ECHOE | ECHOKE | ECHOK | ECHO | ICANON | IEXTEN | ISIG
-    /// cc:     VDISCARD: 15, VEOF: 4, VEOL2: 0, (..)
+    /// line:   Tty
+    /// cc:     VDISCARD: ^O, VEOF: ^D, VEOL2: undef, (..)
     /// ispeed: B38400
     /// ospeed: B38400
     ///
     /// $ echo 'this will panic!' | ./stdin
-    /// called `Result::unwrap()` on an `Err` value: file descriptor is not a TTY
+    /// called `Result::unwrap()` on an `Err` value: NotATty
     /// ```
     ///
     /// The second call panicked because `./stdin`'s stdin was not connected to a TTY, the
@@ -97,18 +206,36 @@ impl Termios {
     ///        |           stderr| => TTY |               stderr| => TTY
     ///        +-----------------+        +---------------------+
     /// ```
-    pub fn fetch(fd: c_int) -> io::Result<Termios> {
+    ///
+    /// To reach the TTY in that case, use
+    /// [`ControllingTerminal`](terminal/struct.ControllingTerminal.html).
+    pub fn fetch(fd: c_int) -> Result<Termios, Error> {
         let mut termios: raw::Termios = Default::default();
 
         unsafe {
-            match raw::tcgetattr(fd, &mut termios) {
-                FAILURE => Err(io::Error::last_os_error()),
-                SUCCESS => Ok(Termios::from_raw(termios)),
+            match get_attr(fd, &mut termios) {
+                FAILURE => Err(Error::last_os_error()),
+                SUCCESS => Termios::try_from_raw(termios),
                 _ => unreachable!(),
             }
         }
     }
 
+    /// Returns the `Termios` structure associated with the standard input
+    pub fn stdin() -> Result<Termios, Error> {
+        Termios::fetch(libc::STDIN_FILENO)
+    }
+
+    /// Returns the `Termios` structure associated with the standard output
+    pub fn stdout() -> Result<Termios, Error> {
+        Termios::fetch(libc::STDOUT_FILENO)
+    }
+
+    /// Returns the `Termios` structure associated with the standard error
+    pub fn stderr() -> Result<Termios, Error> {
+        Termios::fetch(libc::STDERR_FILENO)
+    }
+
     /// Updates the `Termios` structure associated with the `fd` (file descriptor)
     ///
     /// # Examples
@@ -202,123 +329,173 @@ impl Termios {
     /// ```
     ///
     /// If you run this example, you'll receive the `"Got XYZ"` message each time you press a key.
-    pub fn update(&self, fd: c_int, when: When) -> io::Result<()> {
+    ///
+    /// # Restoration
+    ///
+    /// If `restore::install` has been called, the original `Termios` structure of `fd` is saved
+    /// before the first update, check the [`restore`](restore/index.html) module. Failing to save
+    /// it (e.g. because `restore::CAPACITY` file descriptors are already tracked) doesn't make the
+    /// update fail, call `restore::track` beforehand to handle that case.
+    pub fn update(&self, fd: c_int, when: When) -> Result<(), Error> {
+        let _ = restore::track(fd);
+
         unsafe {
-            match raw::tcsetattr(fd, when.to_raw(), self.as_raw()) {
-                FAILURE => Err(io::Error::last_os_error()),
+            match set_attr(fd, when.to_raw(), self.as_raw()) {
+                FAILURE => Err(Error::last_os_error()),
                 SUCCESS => Ok(()),
                 _ => unreachable!(),
             }
         }
     }
 
-    /// Puts `Termios` in "raw" mode
-    ///
-    /// # Examples
-    ///
-    /// Compare "cooked" and "raw" modes
-    ///
-    /// ``` no_run
-    /// // examples/raw.rs
-    /// #![feature(libc)]
-    ///
-    /// extern crate libc;
-    /// extern crate termios;
-    ///
-    /// use termios::prelude::*;
-    ///
-    /// fn main() {
-    ///     let mut termios = Termios::fetch(libc::STDIN_FILENO).unwrap();
-    ///     println!("Cooked:\n{:?}", termios);
-    ///     termios.make_raw();
-    ///     println!("\nRaw:\n{:?}", termios);
-    /// }
-    /// ```
-    ///
-    /// ``` text
-    /// Cooked:
-    /// iflag:  ICRNL | IXON
-    /// oflag:  ONLCR | OPOST
-    /// cflag:  CS8 | CREAD
-    /// lflag:  ECHOCTL | ECHOE | ECHOKE | ECHOK | ECHO | ICANON | IEXTEN | ISIG
-    /// cc:     VDISCARD: 15, VEOF: 4, VEOL2: 0, (..)
-    /// ispeed: B38400
-    /// ospeed: B38400
-    ///
-    /// Raw:
-    /// iflag:
-    /// oflag:  ONLCR
-    /// cflag:  CS8 | CREAD
-    /// lflag:  ECHOCTL | ECHOE | ECHOKE | ECHOK
-    /// cc:     VDISCARD: 15, VEOF: 4, VEOL2: 0, (..)
-    /// ispeed: B38400
-    /// ospeed: B38400
-    /// ```
-    pub fn make_raw(&mut self) {
+    /// Waits until all the output written to `fd` has been transmitted
+    pub fn drain(fd: c_int) -> Result<(), Error> {
         unsafe {
-            raw::cfmakeraw(self.as_raw_mut())
+            match raw::tcdrain(fd) {
+                FAILURE => Err(Error::last_os_error()),
+                SUCCESS => Ok(()),
+                _ => unreachable!(),
+            }
         }
     }
 
-    /// Returns the input baud rate
-    pub fn ispeed(&self) -> BaudRate {
+    /// Discards the data in the `queue` of `fd` that hasn't been transmitted/read yet
+    pub fn flush(fd: c_int, queue: Queue) -> Result<(), Error> {
         unsafe {
-            BaudRate::from_raw(raw::cfgetispeed(self.as_raw()))
+            match raw::tcflush(fd, queue.to_raw()) {
+                FAILURE => Err(Error::last_os_error()),
+                SUCCESS => Ok(()),
+                _ => unreachable!(),
+            }
         }
     }
 
-    /// Returns the output baud rate
-    pub fn ospeed(&self) -> BaudRate {
+    /// Suspends/restarts the transmission or reception of data on `fd`
+    pub fn flow(fd: c_int, action: FlowAction) -> Result<(), Error> {
         unsafe {
-            BaudRate::from_raw(raw::cfgetospeed(self.as_raw()))
+            match raw::tcflow(fd, action.to_raw()) {
+                FAILURE => Err(Error::last_os_error()),
+                SUCCESS => Ok(()),
+                _ => unreachable!(),
+            }
         }
     }
 
-    /// Sets the input baud rate
-    pub fn set_ispeed(&mut self, rate: BaudRate) {
+    /// Transmits a continuous stream of zero-valued bits on `fd` for a specific `duration`
+    ///
+    /// A `duration` of zero sends the break for 0.25 to 0.5 seconds, the meaning of a non-zero
+    /// `duration` is implementation defined.
+    pub fn send_break(fd: c_int, duration: c_int) -> Result<(), Error> {
         unsafe {
-            match raw::cfsetispeed(self.as_raw_mut(), rate.to_raw()) {
-                FAILURE => {
-                    // NB This operation can only panic if self is null (impossible in safe code)
-                    // or if `rate.to_raw()` is an invalid value (that would be a bug)
-                    unreachable!();
-                },
-                SUCCESS => {},
+            match raw::tcsendbreak(fd, duration) {
+                FAILURE => Err(Error::last_os_error()),
+                SUCCESS => Ok(()),
                 _ => unreachable!(),
             }
         }
     }
 
-    /// Sets the output baud rate
-    pub fn set_ospeed(&mut self, rate: BaudRate) {
+    /// Returns the input speed
+    ///
+    /// Fails with `Error::UnknownBaudRate` if the speed field holds a value unknown to this crate.
+    #[cfg(not(target_os = "linux"))]
+    pub fn ispeed(&self) -> Result<Speed, Error> {
+        unsafe {
+            Ok(Speed::from_raw(raw::cfgetispeed(self.as_raw())))
+        }
+    }
+
+    /// Returns the input speed
+    ///
+    /// Fails with `Error::UnknownBaudRate` if the speed field holds a value unknown to this crate.
+    // NB glibc's `cfgetispeed` ignores `CIBAUD`, so the input speed is decoded here
+    #[cfg(target_os = "linux")]
+    pub fn ispeed(&self) -> Result<Speed, Error> {
+        match (self.cflag.bits() >> raw::IBSHIFT) & raw::CBAUD {
+            // Like the kernel, treat an unset `CIBAUD` as "same as the output speed"
+            raw::B0 => self.ospeed(),
+            speed => speed_from_raw(speed, self._ispeed),
+        }
+    }
+
+    /// Returns the output speed
+    ///
+    /// Fails with `Error::UnknownBaudRate` if the speed field holds a value unknown to this crate.
+    #[cfg(not(target_os = "linux"))]
+    pub fn ospeed(&self) -> Result<Speed, Error> {
+        unsafe {
+            Ok(Speed::from_raw(raw::cfgetospeed(self.as_raw())))
+        }
+    }
+
+    /// Returns the output speed
+    ///
+    /// Fails with `Error::UnknownBaudRate` if the speed field holds a value unknown to this crate.
+    #[cfg(target_os = "linux")]
+    pub fn ospeed(&self) -> Result<Speed, Error> {
+        speed_from_raw(unsafe { raw::cfgetospeed(self.as_raw()) }, self._ospeed)
+    }
+
+    /// Sets the input speed
+    #[cfg(not(target_os = "linux"))]
+    pub fn set_ispeed<S>(&mut self, speed: S) where S: Into<Speed> {
+        unsafe {
+            cfset(raw::cfsetispeed, self.as_raw_mut(), speed.into().to_raw())
+        }
+    }
+
+    /// Sets the input speed
+    #[cfg(target_os = "linux")]
+    pub fn set_ispeed<S>(&mut self, speed: S) where S: Into<Speed> {
         unsafe {
-            match raw::cfsetospeed(self.as_raw_mut(), rate.to_raw()) {
-                FAILURE => {
-                    // NB This operation can only panic if self is null (impossible in safe code)
-                    // or if `rate.to_raw()` is an invalid value (that would be a bug)
-                    unreachable!();
+            let termios = self.as_raw_mut();
+
+            termios.c_cflag &= !raw::CIBAUD;
+
+            // NB glibc's `cfsetispeed` would also overwrite the output speed (`CBAUD`)
+            match Speed::from_bits_per_second(speed.into().as_u32()) {
+                Speed::Standard(rate) => termios.c_cflag |= rate.to_raw() << raw::IBSHIFT,
+                Speed::Custom(bps) => {
+                    termios.c_cflag |= raw::BOTHER << raw::IBSHIFT;
+                    termios.c_ispeed = bps;
                 },
-                SUCCESS => {},
-                _ => unreachable!(),
             }
         }
     }
 
-    /// Sets both the input and the output baud rates
-    pub fn set_speed(&mut self, rate: BaudRate) {
+    /// Sets the output speed
+    #[cfg(not(target_os = "linux"))]
+    pub fn set_ospeed<S>(&mut self, speed: S) where S: Into<Speed> {
         unsafe {
-            match raw::cfsetspeed(self.as_raw_mut(), rate.to_raw()) {
-                FAILURE => {
-                    // NB This operation can only panic if self is null (impossible in safe code)
-                    // or if `rate.to_raw()` is an invalid value (that would be a bug)
-                    unreachable!();
+            cfset(raw::cfsetospeed, self.as_raw_mut(), speed.into().to_raw())
+        }
+    }
+
+    /// Sets the output speed
+    #[cfg(target_os = "linux")]
+    pub fn set_ospeed<S>(&mut self, speed: S) where S: Into<Speed> {
+        unsafe {
+            let termios = self.as_raw_mut();
+
+            match Speed::from_bits_per_second(speed.into().as_u32()) {
+                Speed::Standard(rate) => cfset(raw::cfsetospeed, termios, rate.to_raw()),
+                Speed::Custom(bps) => {
+                    termios.c_cflag &= !raw::CBAUD;
+                    termios.c_cflag |= raw::BOTHER;
+                    termios.c_ospeed = bps;
                 },
-                SUCCESS => {},
-                _ => unreachable!(),
             }
         }
     }
 
+    /// Sets both the input and the output speeds
+    pub fn set_speed<S>(&mut self, speed: S) where S: Into<Speed> {
+        let speed = speed.into();
+
+        self.set_ispeed(speed);
+        self.set_ospeed(speed);
+    }
+
     /// Borrows the safe wrapper as its raw representation
     pub fn as_raw(&self) -> &raw::Termios {
         unsafe {
@@ -332,10 +509,23 @@ impl Termios {
     }
 
     /// Puts `raw::Termios` into a safe wrapper without performing any check
+    ///
+    /// The speeds may hold values unknown to this crate, `ispeed` and `ospeed` report them as
+    /// errors. Use `try_from_raw` to reject such structures upfront.
     pub unsafe fn from_raw(termios: raw::Termios) -> Termios {
         mem::transmute(termios)
     }
 
+    /// Puts `raw::Termios` into a safe wrapper, checking that its speeds hold values known by
+    /// this crate
+    pub fn try_from_raw(termios: raw::Termios) -> Result<Termios, Error> {
+        let termios = unsafe { Termios::from_raw(termios) };
+        try!(termios.ispeed());
+        try!(termios.ospeed());
+
+        Ok(termios)
+    }
+
     /// Converts the safe wrapper into its raw representation
     pub fn into_raw(self) -> raw::Termios {
         unsafe {
@@ -351,16 +541,170 @@ impl fmt::Debug for Termios {
         try!(writeln!(f, "oflag:\t{:?}", self.oflag));
         try!(writeln!(f, "cflag:\t{:?}", self.cflag));
         try!(writeln!(f, "lflag:\t{:?}", self.lflag));
+        try!(fmt_line(self, f));
         try!(writeln!(f, "cc:\t{:?}", self.cc));
-        try!(writeln!(f, "ispeed:\t{:?}", self.ispeed()));
-        try!(write!(f, "ospeed:\t{:?}", self.ospeed()));
+        match self.ispeed() {
+            Ok(speed) => try!(writeln!(f, "ispeed:\t{:?}", speed)),
+            Err(e) => try!(writeln!(f, "ispeed:\t{}", e)),
+        }
+        match self.ospeed() {
+            Ok(speed) => try!(write!(f, "ospeed:\t{:?}", speed)),
+            Err(e) => try!(write!(f, "ospeed:\t{}", e)),
+        }
         Ok(())
     }
 }
 
+#[cfg(target_os = "linux")]
+fn fmt_line(termios: &Termios, f: &mut fmt::Formatter) -> fmt::Result {
+    writeln!(f, "line:\t{:?}", termios.line_discipline())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn fmt_line(_: &Termios, _: &mut fmt::Formatter) -> fmt::Result {
+    Ok(())
+}
+
+/// Errors returned by the operations on a terminal
+#[derive(Debug)]
+pub enum Error {
+    /// The file descriptor doesn't refer to a terminal (`ENOTTY`)
+    NotATty,
+    /// The process doesn't have a controlling terminal, see `terminal::ControllingTerminal`
+    NoControllingTerminal,
+    /// The terminal reported a speed that doesn't correspond to any known baud rate
+    UnknownBaudRate(raw::speed_t),
+    /// The process is not in the foreground process group of the terminal, see
+    /// `Termios::update_background`
+    NotInForeground,
+    /// The popped mode is not at the top of the stack, see `stack::Stack::pop`
+    OutOfOrder,
+    /// Some of the requested changes were not applied, see `Termios::update_verified`
+    Unapplied(Vec<verify::Field>),
+    /// `restore::CAPACITY` file descriptors are already being tracked, see `restore::track`
+    RegistryFull,
+    /// Any other error reported by the OS
+    Os(io::Error),
+}
+
+impl Error {
+    fn last_os_error() -> Error {
+        Error::from(io::Error::last_os_error())
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Error {
+        match e.raw_os_error() {
+            Some(libc::ENOTTY) => Error::NotATty,
+            _ => Error::Os(e),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(e: Error) -> io::Error {
+        match e {
+            Error::NotATty => io::Error::from_raw_os_error(libc::ENOTTY),
+            Error::NoControllingTerminal => io::Error::from_raw_os_error(libc::ENXIO),
+            Error::Os(e) => e,
+            e => {
+                let kind = match e {
+                    Error::UnknownBaudRate(_) => io::ErrorKind::InvalidData,
+                    Error::OutOfOrder => io::ErrorKind::InvalidInput,
+                    _ => io::ErrorKind::Other,
+                };
+
+                io::Error::new(kind, e)
+            },
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::NotATty => f.write_str("file descriptor is not a TTY"),
+            Error::NoControllingTerminal => f.write_str("process has no controlling terminal"),
+            Error::UnknownBaudRate(speed) => write!(f, "unknown baud rate: {:#x}", speed),
+            Error::Unapplied(ref fields) => {
+                try!(f.write_str("some of the requested changes were not applied:"));
+
+                for field in fields {
+                    try!(write!(f, " {:?}", field));
+                }
+
+                Ok(())
+            },
+            Error::Os(ref e) => write!(f, "{}", e),
+            _ => f.write_str(error::Error::description(self)),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::NotATty => "file descriptor is not a TTY",
+            Error::NoControllingTerminal => "process has no controlling terminal",
+            Error::UnknownBaudRate(_) => "unknown baud rate",
+            Error::NotInForeground => {
+                "process is not in the foreground process group of the terminal"
+            },
+            Error::OutOfOrder => "the popped mode is not at the top of the stack",
+            Error::Unapplied(_) => "some of the requested changes were not applied",
+            Error::RegistryFull => "too many file descriptors are being tracked",
+            Error::Os(ref e) => e.description(),
+        }
+    }
+
+    fn cause(&self) -> Option<&error::Error> {
+        match *self {
+            Error::Os(ref e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned when parsing a flag, a control char, etc from a string fails
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    kind: &'static str,
+    input: String,
+}
+
+impl ParseError {
+    fn new(kind: &'static str, input: &str) -> ParseError {
+        ParseError {
+            kind: kind,
+            input: input.to_owned(),
+        }
+    }
+
+    /// Returns the string that couldn't be parsed
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown {}: {:?}", self.kind, self.input)
+    }
+}
+
+impl error::Error for ParseError {
+    fn description(&self) -> &str {
+        "failed to parse string"
+    }
+}
+
 /// Standard baud rates
+///
+/// Platform specific rates are only available on the platforms that support them, use
+/// `BaudRate::all()` to list the rates supported by the current target.
 #[allow(missing_docs)]
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum BaudRate {
     B0,
     B50,
@@ -375,19 +719,179 @@ pub enum BaudRate {
     B1800,
     B2400,
     B4800,
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    B7200,
     B9600,
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    B14400,
     B19200,
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    B28800,
     B38400,
     B57600,
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    B76800,
     B115200,
     B230400,
+    #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+    B460800,
+    #[cfg(target_os = "linux")]
+    B500000,
+    #[cfg(target_os = "linux")]
+    B576000,
+    #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+    B921600,
+    #[cfg(target_os = "linux")]
+    B1000000,
+    #[cfg(target_os = "linux")]
+    B1152000,
+    #[cfg(target_os = "linux")]
+    B1500000,
+    #[cfg(target_os = "linux")]
+    B2000000,
+    #[cfg(target_os = "linux")]
+    B2500000,
+    #[cfg(target_os = "linux")]
+    B3000000,
+    #[cfg(target_os = "linux")]
+    B3500000,
+    #[cfg(target_os = "linux")]
+    B4000000,
 }
 
+const BAUD_RATES: [BaudRate; 19] = [
+    B0,
+    B50,
+    B75,
+    B110,
+    B134,
+    B150,
+    B200,
+    B300,
+    B600,
+    B1200,
+    B1800,
+    B2400,
+    B4800,
+    B9600,
+    B19200,
+    B38400,
+    B57600,
+    B115200,
+    B230400,
+];
+
+#[cfg(target_os = "freebsd")]
+const EXTENDED_BAUD_RATES: [BaudRate; 6] = [
+    B7200,
+    B14400,
+    B28800,
+    B76800,
+    B460800,
+    B921600,
+];
+
+#[cfg(target_os = "linux")]
+const EXTENDED_BAUD_RATES: [BaudRate; 12] = [
+    B460800,
+    B500000,
+    B576000,
+    B921600,
+    B1000000,
+    B1152000,
+    B1500000,
+    B2000000,
+    B2500000,
+    B3000000,
+    B3500000,
+    B4000000,
+];
+
+#[cfg(target_os = "macos")]
+const EXTENDED_BAUD_RATES: [BaudRate; 4] = [
+    B7200,
+    B14400,
+    B28800,
+    B76800,
+];
+
 impl BaudRate {
-    fn from_raw(speed: raw::tcflag_t) -> BaudRate {
+    /// Returns all the baud rates supported by the current target, from slowest to fastest
+    pub fn all() -> Vec<BaudRate> {
+        let mut rates = BAUD_RATES.to_vec();
+
+        rates.extend(EXTENDED_BAUD_RATES.iter().cloned());
+        rates.sort_by(|a, b| a.as_u32().cmp(&b.as_u32()));
+        rates
+    }
+
+    /// Returns the baud rate that matches `bps` (bits per second), if it's supported by the
+    /// current target
+    pub fn from_bits_per_second(bps: u32) -> Option<BaudRate> {
+        BaudRate::all().into_iter().find(|rate| rate.as_u32() == bps)
+    }
+
+    /// Returns the baud rate in bits per second
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            B0 => 0,
+            B50 => 50,
+            B75 => 75,
+            B110 => 110,
+            B134 => 134,
+            B150 => 150,
+            B200 => 200,
+            B300 => 300,
+            B600 => 600,
+            B1200 => 1200,
+            B1800 => 1800,
+            B2400 => 2400,
+            B4800 => 4800,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B7200 => 7200,
+            B9600 => 9600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B14400 => 14400,
+            B19200 => 19200,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B28800 => 28800,
+            B38400 => 38400,
+            B57600 => 57600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B76800 => 76800,
+            B115200 => 115200,
+            B230400 => 230400,
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            B460800 => 460800,
+            #[cfg(target_os = "linux")]
+            B500000 => 500000,
+            #[cfg(target_os = "linux")]
+            B576000 => 576000,
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            B921600 => 921600,
+            #[cfg(target_os = "linux")]
+            B1000000 => 1000000,
+            #[cfg(target_os = "linux")]
+            B1152000 => 1152000,
+            #[cfg(target_os = "linux")]
+            B1500000 => 1500000,
+            #[cfg(target_os = "linux")]
+            B2000000 => 2000000,
+            #[cfg(target_os = "linux")]
+            B2500000 => 2500000,
+            #[cfg(target_os = "linux")]
+            B3000000 => 3000000,
+            #[cfg(target_os = "linux")]
+            B3500000 => 3500000,
+            #[cfg(target_os = "linux")]
+            B4000000 => 4000000,
+        }
+    }
+
+    fn from_raw(speed: raw::speed_t) -> Option<BaudRate> {
         use BaudRate::*;
 
-        match speed {
+        Some(match speed {
             raw::B0 => B0,
             raw::B50 => B50,
             raw::B75 => B75,
@@ -401,14 +905,46 @@ impl BaudRate {
             raw::B1800 => B1800,
             raw::B2400 => B2400,
             raw::B4800 => B4800,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            raw::B7200 => B7200,
             raw::B9600 => B9600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            raw::B14400 => B14400,
             raw::B19200 => B19200,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            raw::B28800 => B28800,
             raw::B38400 => B38400,
             raw::B57600 => B57600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            raw::B76800 => B76800,
             raw::B115200 => B115200,
             raw::B230400 => B230400,
-            _ => panic!("Unknown baud rate flag: {}", speed),
-        }
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            raw::B460800 => B460800,
+            #[cfg(target_os = "linux")]
+            raw::B500000 => B500000,
+            #[cfg(target_os = "linux")]
+            raw::B576000 => B576000,
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            raw::B921600 => B921600,
+            #[cfg(target_os = "linux")]
+            raw::B1000000 => B1000000,
+            #[cfg(target_os = "linux")]
+            raw::B1152000 => B1152000,
+            #[cfg(target_os = "linux")]
+            raw::B1500000 => B1500000,
+            #[cfg(target_os = "linux")]
+            raw::B2000000 => B2000000,
+            #[cfg(target_os = "linux")]
+            raw::B2500000 => B2500000,
+            #[cfg(target_os = "linux")]
+            raw::B3000000 => B3000000,
+            #[cfg(target_os = "linux")]
+            raw::B3500000 => B3500000,
+            #[cfg(target_os = "linux")]
+            raw::B4000000 => B4000000,
+            _ => return None,
+        })
     }
 
     fn to_raw(&self) -> raw::speed_t {
@@ -426,17 +962,156 @@ impl BaudRate {
             B1800 => raw::B1800,
             B2400 => raw::B2400,
             B4800 => raw::B4800,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B7200 => raw::B7200,
             B9600 => raw::B9600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B14400 => raw::B14400,
             B19200 => raw::B19200,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B28800 => raw::B28800,
             B38400 => raw::B38400,
             B57600 => raw::B57600,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            B76800 => raw::B76800,
             B115200 => raw::B115200,
             B230400 => raw::B230400,
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            B460800 => raw::B460800,
+            #[cfg(target_os = "linux")]
+            B500000 => raw::B500000,
+            #[cfg(target_os = "linux")]
+            B576000 => raw::B576000,
+            #[cfg(any(target_os = "freebsd", target_os = "linux"))]
+            B921600 => raw::B921600,
+            #[cfg(target_os = "linux")]
+            B1000000 => raw::B1000000,
+            #[cfg(target_os = "linux")]
+            B1152000 => raw::B1152000,
+            #[cfg(target_os = "linux")]
+            B1500000 => raw::B1500000,
+            #[cfg(target_os = "linux")]
+            B2000000 => raw::B2000000,
+            #[cfg(target_os = "linux")]
+            B2500000 => raw::B2500000,
+            #[cfg(target_os = "linux")]
+            B3000000 => raw::B3000000,
+            #[cfg(target_os = "linux")]
+            B3500000 => raw::B3500000,
+            #[cfg(target_os = "linux")]
+            B4000000 => raw::B4000000,
+        }
+    }
+}
+
+impl fmt::Display for BaudRate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_u32())
+    }
+}
+
+impl FromStr for BaudRate {
+    type Err = ParseError;
+
+    /// Parses a baud rate in bits per second, with or without the `B` prefix (e.g. `"9600"` or
+    /// `"B9600"`)
+    fn from_str(s: &str) -> Result<BaudRate, ParseError> {
+        let digits = if s.starts_with('B') { &s[1..] } else { s };
+
+        match digits.parse().ok().and_then(BaudRate::from_bits_per_second) {
+            Some(rate) => Ok(rate),
+            None => Err(ParseError::new("baud rate", s)),
+        }
+    }
+}
+
+/// Input or output speed, either a standard baud rate or a custom one
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub enum Speed {
+    /// A baud rate supported by the current target
+    Standard(BaudRate),
+    /// Any other rate, in bits per second
+    ///
+    /// Custom rates are supported on Linux, through the `termios2` structure and `BOTHER`, and on
+    /// FreeBSD and macOS, where speeds are plain numbers. Either way, the driver may still reject
+    /// a rate it can't generate when the `Termios` structure gets applied.
+    Custom(u32),
+}
+
+impl Speed {
+    /// Returns the standard baud rate that matches `bps` (bits per second), or a custom speed if
+    /// there's none
+    pub fn from_bits_per_second(bps: u32) -> Speed {
+        match BaudRate::from_bits_per_second(bps) {
+            Some(rate) => Speed::Standard(rate),
+            None => Speed::Custom(bps),
+        }
+    }
+
+    /// Returns the speed in bits per second
+    pub fn as_u32(&self) -> u32 {
+        match *self {
+            Speed::Standard(rate) => rate.as_u32(),
+            Speed::Custom(bps) => bps,
+        }
+    }
+
+    // NB On FreeBSD and macOS, `speed_t` is the speed in bits per second
+    #[cfg(not(target_os = "linux"))]
+    fn from_raw(speed: raw::speed_t) -> Speed {
+        match BaudRate::from_raw(speed) {
+            Some(rate) => Speed::Standard(rate),
+            None => Speed::Custom(speed as u32),
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn to_raw(&self) -> raw::speed_t {
+        match *self {
+            Speed::Standard(rate) => rate.to_raw(),
+            Speed::Custom(bps) => bps as raw::speed_t,
+        }
+    }
+}
+
+impl From<BaudRate> for Speed {
+    fn from(rate: BaudRate) -> Speed {
+        Speed::Standard(rate)
+    }
+}
+
+impl fmt::Debug for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Speed::Standard(rate) => write!(f, "{:?}", rate),
+            Speed::Custom(bps) => write!(f, "{}", bps),
+        }
+    }
+}
+
+impl fmt::Display for Speed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_u32())
+    }
+}
+
+impl FromStr for Speed {
+    type Err = ParseError;
+
+    /// Parses a speed in bits per second, with or without the `B` prefix (e.g. `"9600"` or
+    /// `"250000"`)
+    fn from_str(s: &str) -> Result<Speed, ParseError> {
+        let digits = if s.starts_with('B') { &s[1..] } else { s };
+
+        match digits.parse() {
+            Ok(bps) => Ok(Speed::from_bits_per_second(bps)),
+            Err(_) => Err(ParseError::new("speed", s)),
         }
     }
 }
 
 /// When to update the underlying `raw::Termios` structure
+#[derive(Clone, Copy)]
 pub enum When {
     /// Make change immediate
     Now,
@@ -456,12 +1131,454 @@ impl When {
     }
 }
 
+/// Queue to discard with `Termios::flush`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Queue {
+    /// Data received but not read
+    Input,
+    /// Data written but not transmitted
+    Output,
+    /// Both of the above
+    Both,
+}
+
+impl Queue {
+    fn to_raw(&self) -> c_int {
+        match *self {
+            Input => raw::TCIFLUSH,
+            Output => raw::TCOFLUSH,
+            Both => raw::TCIOFLUSH,
+        }
+    }
+}
+
+/// Action performed by `Termios::flow`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FlowAction {
+    /// Suspend output
+    SuspendOutput,
+    /// Restart suspended output
+    ResumeOutput,
+    /// Transmit a STOP character, intended to suspend input
+    SendStop,
+    /// Transmit a START character, intended to restart input
+    SendStart,
+}
+
+impl FlowAction {
+    fn to_raw(&self) -> c_int {
+        match *self {
+            SuspendOutput => raw::TCOOFF,
+            ResumeOutput => raw::TCOON,
+            SendStop => raw::TCIOFF,
+            SendStart => raw::TCION,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use libc;
 
     use Termios;
 
+    #[test]
+    fn diff() {
+        use control::Char;
+        use output::TabDelay;
+        use traits::Set;
+
+        let mut old = Termios::fetch(libc::STDIN_FILENO).unwrap();
+        old.set(TabDelay::Tab0);
+        old.cc[Char::VINTR] = 3;
+        let mut new = old;
+        new.make_raw();
+        new.set(TabDelay::Tab3);
+        new.cc.disable(Char::VINTR);
+        unsafe {
+            new.as_raw_mut().c_iflag |= 1 << 30;
+        }
+
+        let diff = old.diff(&new);
+        assert!(!diff.is_empty());
+        assert!(diff.to_string().contains("+0x40000000"));
+        assert!(diff.to_string().contains("VINTR: ^C -> undef"));
+
+        let mut patched = old;
+        diff.apply(&mut patched);
+        assert_eq!(patched, new);
+    }
+
+    #[test]
+    fn chars() {
+        use control::{Caret, Char};
+
+        let mut termios = Termios::stdin().unwrap();
+
+        termios.cc[Char::VINTR] = 7;
+        assert_eq!(termios.cc.get(Char::VINTR), Some(7));
+
+        termios.cc.disable(Char::VINTR);
+        assert!(termios.cc.is_disabled(Char::VINTR));
+        assert_eq!("^G".parse(), Ok(Caret(Some(7))));
+
+        for byte in 0..256 {
+            let caret = Caret(Some(byte as u8));
+
+            assert_eq!(caret.to_string().parse(), Ok(caret));
+        }
+    }
+
+    #[test]
+    fn read_policy() {
+        use std::time::Duration;
+
+        use control::ReadPolicy;
+        use traits::{Get, Set};
+
+        let mut termios = Termios::stdin().unwrap();
+        let policy = ReadPolicy::inter_byte(4, Duration::from_millis(250)).unwrap();
+
+        termios.set(policy);
+        assert_eq!(termios.get::<ReadPolicy>(),
+                   ReadPolicy::InterByte(4, Duration::from_millis(300)));
+
+        termios.set(ReadPolicy::Poll);
+        assert_eq!(termios.get::<ReadPolicy>(), ReadPolicy::Poll);
+
+        // Values out of range saturate
+        termios.set(ReadPolicy::Blocking(0));
+        assert_eq!(termios.get::<ReadPolicy>(), ReadPolicy::Blocking(1));
+        termios.set(ReadPolicy::Timeout(Duration::from_secs(30)));
+        assert_eq!(termios.get::<ReadPolicy>(),
+                   ReadPolicy::Timeout(Duration::from_millis(25500)));
+        termios.set(ReadPolicy::InterByte(0, Duration::from_secs(0)));
+        assert_eq!(termios.get::<ReadPolicy>(),
+                   ReadPolicy::InterByte(1, Duration::from_millis(100)));
+
+        assert!(ReadPolicy::blocking(0).is_none());
+        assert!(ReadPolicy::inter_byte(0, Duration::from_millis(100)).is_none());
+        assert!(ReadPolicy::timeout(Duration::from_millis(0)).is_none());
+        assert!(ReadPolicy::timeout(Duration::from_millis(25600)).is_none());
+        assert!(ReadPolicy::timeout(Duration::from_millis(25500)).is_some());
+    }
+
+    #[test]
+    fn newline() {
+        use input::{self, InputNewline};
+        use output::{self, OutputNewline};
+        use traits::{Clear, Contains, Get, Set};
+
+        let mut termios = Termios::stdin().unwrap();
+
+        termios.set(InputNewline::Cr);
+        termios.set(OutputNewline::CrLf);
+        assert_eq!(termios.get::<InputNewline>(), InputNewline::Cr);
+        assert_eq!(termios.get::<OutputNewline>(), OutputNewline::CrLf);
+
+        termios.set(input::Flag::INLCR);
+        assert_eq!(termios.get::<InputNewline>(),
+                   InputNewline::Conflicting(input::Flag::ICRNL | input::Flag::INLCR));
+
+        termios.set(InputNewline::Ignore);
+        assert_eq!(termios.get::<InputNewline>(), InputNewline::Ignore);
+
+        // Nothing is translated without `OPOST`, whatever `ONLCR` says
+        termios.clear(output::Flag::OPOST);
+        termios.set(output::Flag::ONOCR);
+        assert_eq!(termios.get::<OutputNewline>(), OutputNewline::Unprocessed);
+        termios.set(OutputNewline::Unprocessed);
+        assert!(termios.contains(output::Flag::ONLCR));
+
+        termios.set(OutputNewline::CrToLf);
+        assert_eq!(termios.get::<OutputNewline>(), OutputNewline::CrToLf);
+        assert!(termios.contains(output::Flag::OPOST));
+        assert!(termios.contains(output::Flag::ONOCR));
+
+        termios.set(output::Flag::ONLCR);
+        assert_eq!(termios.get::<OutputNewline>(),
+                   OutputNewline::Conflicting(output::Flag::OCRNL | output::Flag::ONLCR));
+
+        termios.set(OutputNewline::Lf);
+        assert_eq!(termios.get::<OutputNewline>(), OutputNewline::Lf);
+    }
+
+    #[test]
+    fn serial() {
+        use control::{CSIZE, FlowControl, Parity, StopBits};
+        use input;
+        use traits::{Contains, Get, Set};
+
+        let mut termios = Termios::stdin().unwrap();
+
+        termios.set(CSIZE::CS7);
+        termios.set(Parity::Even);
+        termios.set(StopBits::Two);
+        termios.set(FlowControl::Hardware);
+        assert_eq!(termios.get::<Parity>(), Parity::Even);
+        assert_eq!(termios.get::<StopBits>(), StopBits::Two);
+        assert_eq!(termios.get::<FlowControl>(), FlowControl::Hardware);
+
+        termios.set(input::Flag::INPCK);
+        termios.set(Parity::None);
+        termios.set(FlowControl::Software);
+        assert_eq!(termios.get::<Parity>(), Parity::None);
+        assert_eq!(termios.get::<FlowControl>(), FlowControl::Software);
+        assert!(termios.contains(input::Flag::INPCK));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn delays() {
+        use output::{BackspaceDelay, CarriageReturnDelay, FormFeedDelay, NewlineDelay, TabDelay,
+                     VerticalTabDelay};
+        use traits::{Get, Set};
+
+        let mut termios = Termios::stdin().unwrap();
+
+        termios.set(NewlineDelay::Nl1);
+        termios.set(CarriageReturnDelay::Cr2);
+        termios.set(TabDelay::Tab3);
+        termios.set(BackspaceDelay::Bs1);
+        termios.set(VerticalTabDelay::Vt1);
+        termios.set(FormFeedDelay::Ff1);
+        assert_eq!(termios.get::<NewlineDelay>(), NewlineDelay::Nl1);
+        assert_eq!(termios.get::<CarriageReturnDelay>(), CarriageReturnDelay::Cr2);
+        assert_eq!(termios.get::<TabDelay>(), TabDelay::Tab3);
+        assert_eq!(termios.get::<BackspaceDelay>(), BackspaceDelay::Bs1);
+        assert_eq!(termios.get::<VerticalTabDelay>(), VerticalTabDelay::Vt1);
+        assert_eq!(termios.get::<FormFeedDelay>(), FormFeedDelay::Ff1);
+
+        termios.set(CarriageReturnDelay::Cr0);
+        termios.set(TabDelay::Tab1);
+        assert_eq!(termios.get::<CarriageReturnDelay>(), CarriageReturnDelay::Cr0);
+        assert_eq!(termios.get::<TabDelay>(), TabDelay::Tab1);
+        assert_eq!(termios.get::<NewlineDelay>(), NewlineDelay::Nl1);
+    }
+
+    #[test]
+    fn unknown_delays() {
+        use output::TabDelay;
+        use raw;
+        use traits::{Get, Set};
+
+        let mut termios = Termios::stdin().unwrap();
+
+        // Every value of the field can be decoded, e.g. `TAB1 | OXTABS` on macOS
+        unsafe {
+            termios.as_raw_mut().c_oflag |= raw::TABDLY;
+        }
+        let delay = termios.get::<TabDelay>();
+        assert!(!format!("{:?}", termios).is_empty());
+
+        termios.set(TabDelay::Tab0);
+        termios.set(delay);
+        assert_eq!(termios.get::<TabDelay>(), delay);
+    }
+
+    #[test]
+    fn flags() {
+        use input::Flag::*;
+        use input::Flags;
+
+        let mut flags = ICRNL | IXON;
+        assert!(flags.contains_all(ICRNL | IXON));
+        assert!(!flags.contains_any(IGNCR | INLCR));
+
+        flags.toggle(IXON);
+        flags.insert(IGNCR);
+        assert_eq!(flags, ICRNL | IGNCR);
+        assert_eq!(flags.iter().collect::<Vec<_>>(), vec![ICRNL, IGNCR]);
+
+        flags.set(ICRNL | IGNCR, false);
+        assert_eq!(flags, Flags::empty());
+        assert_eq!(flags.unknown_bits(), 0);
+
+        // The speeds that Linux stores in the control flags are not unknown bits
+        assert_eq!(Termios::stdin().unwrap().cflag.unknown_bits(), 0);
+    }
+
+    #[test]
+    fn parse() {
+        use control::Char;
+        use input;
+
+        assert_eq!("icrnl".parse::<input::Flag>(), Ok(input::Flag::ICRNL));
+        assert_eq!("VINTR".parse::<Char>(), Ok(Char::VINTR));
+        assert!("onlcr".parse::<input::Flag>().is_err());
+
+        for flag in input::Flag::all() {
+            assert_eq!(flag.name().parse::<input::Flag>(), Ok(flag));
+        }
+    }
+
+    #[test]
+    fn baud_rate() {
+        use BaudRate;
+
+        assert_eq!(BaudRate::from_bits_per_second(9600), Some(BaudRate::B9600));
+        assert_eq!(BaudRate::from_bits_per_second(9601), None);
+        assert_eq!("B115200".parse(), Ok(BaudRate::B115200));
+        assert_eq!(BaudRate::B38400.to_string(), "38400");
+
+        for rate in BaudRate::all() {
+            assert_eq!(rate.to_string().parse(), Ok(rate));
+        }
+    }
+
+    #[test]
+    fn speed() {
+        use {BaudRate, Speed};
+
+        let mut termios = Termios::fetch(libc::STDIN_FILENO).unwrap();
+
+        termios.set_speed(BaudRate::B9600);
+        assert_eq!(termios.ispeed().unwrap(), Speed::Standard(BaudRate::B9600));
+
+        termios.set_ospeed(Speed::Custom(250000));
+        assert_eq!(termios.ospeed().unwrap(), Speed::Custom(250000));
+
+        termios.set_ispeed(Speed::Custom(9600));
+        assert_eq!(termios.ispeed().unwrap(), Speed::Standard(BaudRate::B9600));
+        assert_eq!(termios.ospeed().unwrap(), Speed::Custom(250000));
+
+        termios.set_ispeed(BaudRate::B115200);
+        assert_eq!(termios.ispeed().unwrap(), Speed::Standard(BaudRate::B115200));
+        assert_eq!(termios.ospeed().unwrap(), Speed::Custom(250000));
+
+        assert_eq!("31250".parse(), Ok(Speed::Custom(31250)));
+    }
+
+    #[test]
+    fn mode() {
+        use input;
+        use local;
+        use mode::Mode;
+        use traits::{Clear, Set};
+
+        let mut termios = Termios::fetch(libc::STDIN_FILENO).unwrap();
+
+        termios.make_sane();
+        assert_eq!(termios.mode(), Mode::Cooked);
+
+        // A typical login terminal, e.g. `iflag: ICRNL | IXON`
+        termios.clear(input::Flag::BRKINT);
+        termios.clear(input::Flag::IMAXBEL);
+        termios.set(input::Flag::IXON);
+        termios.clear(local::Flag::ECHOCTL);
+        termios.clear(local::Flag::ECHOKE);
+        assert_eq!(termios.mode(), Mode::Cooked);
+
+        termios.make_cbreak();
+        assert_eq!(termios.mode(), Mode::Cbreak);
+
+        termios.make_raw();
+        assert_eq!(termios.mode(), Mode::Raw);
+        assert!(termios.is_eight_bit_clean());
+    }
+
+    #[test]
+    fn not_a_tty() {
+        use std::fs::File;
+        use std::os::unix::io::AsRawFd;
+
+        use Error;
+
+        let file = File::open("Cargo.toml").unwrap();
+
+        match Termios::fetch(file.as_raw_fd()) {
+            Err(Error::NotATty) => {},
+            result => panic!("expected `NotATty`, got {:?}", result),
+        }
+    }
+
+    #[test]
+    fn terminal() {
+        use std::fs::{File, OpenOptions};
+        use std::io;
+
+        use Queue;
+        use terminal::Terminal;
+
+        let stdin = io::stdin();
+        assert!(stdin.is_terminal());
+        assert!(stdin.termios().unwrap().diff(&Termios::stdin().unwrap()).is_empty());
+
+        assert!(!File::open("Cargo.toml").unwrap().is_terminal());
+
+        let pty = OpenOptions::new().read(true).write(true).open("/dev/ptmx").unwrap();
+        pty.discard_queue(Queue::Both).unwrap();
+        pty.drain().unwrap();
+        assert!(!pty.is_termios_tracked());
+    }
+
+    #[test]
+    fn controlling_terminal() {
+        use terminal::{ControllingTerminal, Terminal};
+
+        let tty = ControllingTerminal::open().unwrap();
+        assert!(tty.is_terminal());
+        tty.termios().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn line_discipline() {
+        use std::fs::OpenOptions;
+        use std::os::unix::io::AsRawFd;
+
+        use discipline::{self, LineDiscipline};
+        use terminal::Terminal;
+
+        let pty = OpenOptions::new().read(true).write(true).open("/dev/ptmx").unwrap();
+        let fd = pty.as_raw_fd();
+
+        assert_eq!(discipline::fetch(fd).unwrap(), LineDiscipline::Tty);
+        assert_eq!(Termios::fetch(fd).unwrap().line_discipline(), LineDiscipline::Tty);
+        discipline::update(fd, LineDiscipline::Tty).unwrap();
+
+        assert_eq!(pty.line_discipline().unwrap(), LineDiscipline::Tty);
+        pty.set_line_discipline(LineDiscipline::Tty).unwrap();
+    }
+
+    #[test]
+    fn stack() {
+        use std::fs::OpenOptions;
+        use std::os::unix::io::AsRawFd;
+
+        use Error;
+        use local;
+        use stack::Stack;
+        use traits::{Clear, Contains};
+
+        let pty = OpenOptions::new().read(true).write(true).open("/dev/ptmx").unwrap();
+        let fd = pty.as_raw_fd();
+        let mut stack = Stack::new(fd).unwrap();
+
+        let mut cbreak = *stack.top();
+        cbreak.clear(local::Flag::ICANON);
+        let cbreak = stack.push(cbreak).unwrap();
+
+        let mut no_echo = *stack.top();
+        no_echo.clear(local::Flag::ECHO);
+        let no_echo = stack.push(no_echo).unwrap();
+
+        // Popping out of order leaves both the stack and the terminal untouched
+        let top = *stack.top();
+        match stack.pop(&cbreak) {
+            Err(Error::OutOfOrder) => {},
+            result => panic!("expected `OutOfOrder`, got {:?}", result),
+        }
+        assert_eq!(stack.depth(), 2);
+        assert!(stack.top().diff(&top).is_empty());
+        assert!(!Termios::fetch(fd).unwrap().contains(local::Flag::ECHO));
+
+        stack.pop(&no_echo).unwrap();
+        stack.pop(&cbreak).unwrap();
+        assert_eq!(stack.depth(), 0);
+        assert!(Termios::fetch(fd).unwrap().contains(local::Flag::ICANON));
+    }
+
     #[test]
     fn stderr() {
         Termios::fetch(libc::STDERR_FILENO).unwrap();
@@ -1,5 +1,10 @@
+#include <linux/tty.h>
+#include <signal.h>
 #include <stdint.h>
+#include <stdlib.h>
+#include <sys/ioctl.h>
 #include <termios.h>
+#include <unistd.h>
 
 // Special control characters
 enum {
@@ -57,6 +62,42 @@ enum {
     OFDEL_ = OFDEL,
 };
 
+// Output delays
+enum {
+    __ODELAY = (tcflag_t)(-1),
+    NLDLY_ = NLDLY,
+    NL0_ = NL0,
+    NL1_ = NL1,
+    CRDLY_ = CRDLY,
+    CR0_ = CR0,
+    CR1_ = CR1,
+    CR2_ = CR2,
+    CR3_ = CR3,
+    TABDLY_ = TABDLY,
+    TAB0_ = TAB0,
+    TAB1_ = TAB1,
+    TAB2_ = TAB2,
+    TAB3_ = TAB3,
+    BSDLY_ = BSDLY,
+    BS0_ = BS0,
+    BS1_ = BS1,
+    VTDLY_ = VTDLY,
+    VT0_ = VT0,
+    VT1_ = VT1,
+    FFDLY_ = FFDLY,
+    FF0_ = FF0,
+    FF1_ = FF1,
+};
+
+// Not exposed by glibc, values from the kernel's `asm-generic/termbits.h`
+#ifndef BOTHER
+#define BOTHER 0010000
+#endif
+
+#ifndef IBSHIFT
+#define IBSHIFT 16
+#endif
+
 // Control flags
 enum {
     __CFLAG = (tcflag_t)(-1),
@@ -71,11 +112,37 @@ enum {
     PARODD_ = PARODD,
     HUPCL_ = HUPCL,
     CLOCAL_ = CLOCAL,
+    CBAUD_ = CBAUD,
+    CBAUDEX_ = CBAUDEX,
+    BOTHER_ = BOTHER,
     CIBAUD_ = CIBAUD,
     CMSPAR_ = CMSPAR,
     CRTSCTS_ = CRTSCTS,
 };
 
+// NB The kernel's `asm/termbits.h` can't be included alongside `termios.h`, so this is a copy of
+// its `struct termios2`
+struct termios2 {
+    tcflag_t c_iflag;
+    tcflag_t c_oflag;
+    tcflag_t c_cflag;
+    tcflag_t c_lflag;
+    cc_t c_line;
+    cc_t c_cc[19];
+    speed_t c_ispeed;
+    speed_t c_ospeed;
+};
+
+// termios2
+enum {
+    __TERMIOS2 = (unsigned int)(-1),
+    IBSHIFT_ = IBSHIFT,
+    TCGETS2_ = TCGETS2,
+    TCSETS2_ = TCSETS2,
+    TCSETSW2_ = TCSETSW2,
+    TCSETSF2_ = TCSETSF2,
+};
+
 // Local flags
 enum {
     __LFLAG = (tcflag_t)(-1),
@@ -159,3 +226,71 @@ enum {
     B3500000_ = B3500000,
     B4000000_ = B4000000,
 };
+
+// Signals
+enum {
+    __SIGNAL = (int)(-1),
+    SIGHUP_ = SIGHUP,
+    SIGINT_ = SIGINT,
+    SIGQUIT_ = SIGQUIT,
+    SIGTERM_ = SIGTERM,
+    SIGTSTP_ = SIGTSTP,
+    SIGTTOU_ = SIGTTOU,
+};
+
+// sigprocmask
+enum {
+    __SIGPROCMASK = (int)(-1),
+    SIG_BLOCK_ = SIG_BLOCK,
+    SIG_SETMASK_ = SIG_SETMASK,
+    SIG_UNBLOCK_ = SIG_UNBLOCK,
+};
+
+// Signal dispositions
+enum {
+    __SIGHANDLER = (uintptr_t)(-1),
+    SIG_IGN_ = (uintptr_t)SIG_IGN,
+};
+
+// Disabled control character
+enum {
+    __VDISABLE = (int)(-1),
+    _POSIX_VDISABLE_ = _POSIX_VDISABLE,
+};
+
+// Line disciplines
+enum {
+    __LDISC = (int)(-1),
+    N_TTY_ = N_TTY,
+    N_SLIP_ = N_SLIP,
+    N_MOUSE_ = N_MOUSE,
+    N_PPP_ = N_PPP,
+    N_STRIP_ = N_STRIP,
+    N_AX25_ = N_AX25,
+    N_X25_ = N_X25,
+    N_6PACK_ = N_6PACK,
+    N_MASC_ = N_MASC,
+    N_R3964_ = N_R3964,
+    N_PROFIBUS_FDL_ = N_PROFIBUS_FDL,
+    N_IRDA_ = N_IRDA,
+    N_SMSBLOCK_ = N_SMSBLOCK,
+    N_HDLC_ = N_HDLC,
+    N_SYNC_PPP_ = N_SYNC_PPP,
+    N_HCI_ = N_HCI,
+    N_GIGASET_M101_ = N_GIGASET_M101,
+    N_SLCAN_ = N_SLCAN,
+    N_PPS_ = N_PPS,
+    N_V253_ = N_V253,
+    N_CAIF_ = N_CAIF,
+    N_GSM0710_ = N_GSM0710,
+    N_TI_WL_ = N_TI_WL,
+    N_TRACESINK_ = N_TRACESINK,
+    N_TRACEROUTER_ = N_TRACEROUTER,
+};
+
+// Line discipline ioctls
+enum {
+    __TIOCXETD = (int)(-1),
+    TIOCGETD_ = TIOCGETD,
+    TIOCSETD_ = TIOCSETD,
+};
@@ -1,22 +1,25 @@
 //! Dumping ground for other state
 
 use std::fmt;
+use std::str::FromStr;
 
-use Termios;
+use {ParseError, Termios};
 use raw::{tcflag_t, self};
 use self::Flag::*;
 use traits::{Clear, Contains, Set};
 
 /// Local flags
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 #[repr(C)]
 pub struct Flags(tcflag_t);
 
+flags!(0);
+
 impl fmt::Debug for Flags {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
 
-        for &flag in &FLAGS {
+        for flag in Flag::all() {
             let value = flag.to_raw();
 
             if self.0 & value == value {
@@ -30,6 +33,16 @@ impl fmt::Debug for Flags {
             }
         }
 
+        let unknown = self.unknown_bits();
+
+        if unknown != 0 {
+            if is_first {
+                try!(write!(f, "{:#x}", unknown));
+            } else {
+                try!(write!(f, " | {:#x}", unknown));
+            }
+        }
+
         Ok(())
     }
 }
@@ -52,9 +65,32 @@ const FLAGS: [Flag; 15] = [
     TOSTOP,
 ];
 
+#[cfg(target_os = "freebsd")]
+const EXTENSIONS: [Flag; 2] = [
+    ALTWERASE,
+    NOKERNINFO,
+];
+
+#[cfg(target_os = "linux")]
+const EXTENSIONS: [Flag; 1] = [
+    XCASE,
+];
+
+#[cfg(target_os = "macos")]
+const EXTENSIONS: [Flag; 2] = [
+    ALTWERASE,
+    NOKERNINFO,
+];
+
 /// Standard local flags
-#[derive(Clone, Copy, Debug)]
+///
+/// Platform specific flags are only available on the platforms that support them, use
+/// `Flag::all()` to list the flags supported by the current target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Flag {
+    /// Use alternate WERASE algorithm
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    ALTWERASE,
     /// Echo control chars as `^(Char)`
     ECHOCTL,
     /// Visually erase chars
@@ -81,15 +117,90 @@ pub enum Flag {
     ISIG,
     /// Don't flush after interrupt
     NOFLSH,
+    /// No kernel output from VSTATUS
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    NOKERNINFO,
     /// XXX retype pending input (state)
     PENDIN,
     /// Stop background jobs from output
     TOSTOP,
+    /// Canonical upper/lower presentation
+    #[cfg(target_os = "linux")]
+    XCASE,
 }
 
 impl Flag {
+    /// Returns all the flags supported by the current target, in the order `Debug` prints them
+    pub fn all() -> Vec<Flag> {
+        FLAGS.iter().chain(EXTENSIONS.iter()).cloned().collect()
+    }
+
+    /// Returns the name of the flag, e.g. `"ECHO"`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ALTWERASE => "ALTWERASE",
+            ECHO => "ECHO",
+            ECHOCTL => "ECHOCTL",
+            ECHOE => "ECHOE",
+            ECHOK => "ECHOK",
+            ECHOKE => "ECHOKE",
+            ECHONL => "ECHONL",
+            ECHOPRT => "ECHOPRT",
+            EXTPROC => "EXTPROC",
+            FLUSHO => "FLUSHO",
+            ICANON => "ICANON",
+            IEXTEN => "IEXTEN",
+            ISIG => "ISIG",
+            NOFLSH => "NOFLSH",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            NOKERNINFO => "NOKERNINFO",
+            PENDIN => "PENDIN",
+            TOSTOP => "TOSTOP",
+            #[cfg(target_os = "linux")]
+            XCASE => "XCASE",
+        }
+    }
+
+    /// Returns a short description of the flag
+    pub fn description(&self) -> &'static str {
+        match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ALTWERASE => "Use alternate WERASE algorithm",
+            ECHO => "Enable echoing",
+            ECHOCTL => "Echo control chars as `^(Char)`",
+            ECHOE => "Visually erase chars",
+            ECHOK => "Echo NL after line kill",
+            ECHOKE => "Visual erase for line kill",
+            ECHONL => "Echo NL even if ECHO is off",
+            ECHOPRT => "Visual erase mode for hardcopy",
+            EXTPROC => "External processing",
+            FLUSHO => "Output being flushed (state)",
+            ICANON => "Canonalize input lines",
+            IEXTEN => "Enable DISCARD and LNEXt",
+            ISIG => "Enable signals INTR, QUIT, [D]SUSP",
+            NOFLSH => "Don't flush after interrupt",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            NOKERNINFO => "No kernel output from VSTATUS",
+            PENDIN => "XXX retype pending input (state)",
+            TOSTOP => "Stop background jobs from output",
+            #[cfg(target_os = "linux")]
+            XCASE => "Canonical upper/lower presentation",
+        }
+    }
+
+    /// Checks if the flag is specified by POSIX, otherwise it's an extension
+    pub fn is_posix(&self) -> bool {
+        match *self {
+            ECHO | ECHOE | ECHOK | ECHONL | ICANON | IEXTEN | ISIG | NOFLSH | TOSTOP => true,
+            _ => false,
+        }
+    }
+
     fn to_raw(&self) -> tcflag_t {
         match *self {
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ALTWERASE => raw::ALTWERASE,
             ECHO => raw::ECHO,
             ECHOCTL => raw::ECHOCTL,
             ECHOE => raw::ECHOE,
@@ -103,8 +214,26 @@ impl Flag {
             IEXTEN => raw::IEXTEN,
             ISIG => raw::ISIG,
             NOFLSH => raw::NOFLSH,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            NOKERNINFO => raw::NOKERNINFO,
             PENDIN => raw::PENDIN,
             TOSTOP => raw::TOSTOP,
+            #[cfg(target_os = "linux")]
+            XCASE => raw::XCASE,
+        }
+    }
+}
+
+impl FromStr for Flag {
+    type Err = ParseError;
+
+    /// Parses the name of the flag, case insensitively (e.g. `"echo"`)
+    fn from_str(s: &str) -> Result<Flag, ParseError> {
+        let name = s.to_uppercase();
+
+        match Flag::all().into_iter().find(|x| x.name() == name) {
+            Some(x) => Ok(x),
+            None => Err(ParseError::new("local flag", s)),
         }
     }
 }
@@ -1,5 +1,8 @@
+#include <signal.h>
 #include <stdint.h>
+#include <stdlib.h>
 #include <termios.h>
+#include <unistd.h>
 
 // Special control characters
 enum {
@@ -65,6 +68,29 @@ enum {
     OFDEL_ = OFDEL,
 };
 
+// Output delays
+enum {
+    __ODELAY = (tcflag_t)(-1),
+    NL0_ = NL0,
+    NL1_ = NL1,
+    NL2_ = NL2,
+    NL3_ = NL3,
+    CR0_ = CR0,
+    CR1_ = CR1,
+    CR2_ = CR2,
+    CR3_ = CR3,
+    TAB0_ = TAB0,
+    TAB1_ = TAB1,
+    TAB2_ = TAB2,
+    TAB3_ = TAB3,
+    BS0_ = BS0,
+    BS1_ = BS1,
+    VT0_ = VT0,
+    VT1_ = VT1,
+    FF0_ = FF0,
+    FF1_ = FF1,
+};
+
 // Control flags
 enum {
     __CFLAG = (tcflag_t)(-1),
@@ -166,3 +192,34 @@ enum {
     EXTA_ = EXTA,
     EXTB_ = EXTB,
 };
+
+// Signals
+enum {
+    __SIGNAL = (int)(-1),
+    SIGHUP_ = SIGHUP,
+    SIGINT_ = SIGINT,
+    SIGQUIT_ = SIGQUIT,
+    SIGTERM_ = SIGTERM,
+    SIGTSTP_ = SIGTSTP,
+    SIGTTOU_ = SIGTTOU,
+};
+
+// sigprocmask
+enum {
+    __SIGPROCMASK = (int)(-1),
+    SIG_BLOCK_ = SIG_BLOCK,
+    SIG_SETMASK_ = SIG_SETMASK,
+    SIG_UNBLOCK_ = SIG_UNBLOCK,
+};
+
+// Signal dispositions
+enum {
+    __SIGHANDLER = (uintptr_t)(-1),
+    SIG_IGN_ = (uintptr_t)SIG_IGN,
+};
+
+// Disabled control character
+enum {
+    __VDISABLE = (int)(-1),
+    _POSIX_VDISABLE_ = _POSIX_VDISABLE,
+};
@@ -0,0 +1,126 @@
+// Set operations shared by the `Flags` structs of the `control`, `input`, `local` and `output`
+// modules. `$masks` are the multi-bit fields (e.g. `CSIZE`) that are known but not a `Flag`.
+macro_rules! flags {
+    ($masks:expr) => {
+        impl Flags {
+            /// Returns an empty set of flags
+            pub fn empty() -> Flags {
+                Flags(0)
+            }
+
+            /// Returns the raw representation of the flags, including unknown bits
+            pub fn bits(&self) -> ::raw::tcflag_t {
+                self.0
+            }
+
+            /// Returns the bits that don't correspond to any flag known by this crate
+            pub fn unknown_bits(&self) -> ::raw::tcflag_t {
+                let known = Flag::all().iter().fold($masks, |known, flag| known | flag.to_raw());
+
+                self.0 & !known
+            }
+
+            /// Checks if no bit is set
+            pub fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            /// Checks if `flag` is set
+            pub fn contains(&self, flag: Flag) -> bool {
+                let flag = flag.to_raw();
+
+                self.0 & flag == flag
+            }
+
+            /// Checks if all the `flags` are set
+            pub fn contains_all<F>(&self, flags: F) -> bool where F: Into<Flags> {
+                let flags = flags.into().0;
+
+                self.0 & flags == flags
+            }
+
+            /// Checks if any of the `flags` is set
+            pub fn contains_any<F>(&self, flags: F) -> bool where F: Into<Flags> {
+                self.0 & flags.into().0 != 0
+            }
+
+            /// Sets the `flags`
+            pub fn insert<F>(&mut self, flags: F) where F: Into<Flags> {
+                self.0 |= flags.into().0
+            }
+
+            /// Clears the `flags`
+            pub fn remove<F>(&mut self, flags: F) where F: Into<Flags> {
+                self.0 &= !flags.into().0
+            }
+
+            /// Flips the `flags`
+            pub fn toggle<F>(&mut self, flags: F) where F: Into<Flags> {
+                self.0 ^= flags.into().0
+            }
+
+            /// Sets (`value = true`) or clears (`value = false`) the `flags`
+            pub fn set<F>(&mut self, flags: F, value: bool) where F: Into<Flags> {
+                if value {
+                    self.insert(flags)
+                } else {
+                    self.remove(flags)
+                }
+            }
+
+            /// Returns an iterator over the flags that are set
+            pub fn iter(&self) -> ::std::vec::IntoIter<Flag> {
+                let mut flags = Flag::all();
+
+                flags.retain(|&flag| self.contains(flag));
+                flags.into_iter()
+            }
+        }
+
+        impl From<Flag> for Flags {
+            fn from(flag: Flag) -> Flags {
+                Flags(flag.to_raw())
+            }
+        }
+
+        impl ::std::ops::BitOr<Flag> for Flag {
+            type Output = Flags;
+
+            fn bitor(self, rhs: Flag) -> Flags {
+                Flags(self.to_raw() | rhs.to_raw())
+            }
+        }
+
+        impl ::std::ops::BitOr<Flag> for Flags {
+            type Output = Flags;
+
+            fn bitor(self, rhs: Flag) -> Flags {
+                Flags(self.0 | rhs.to_raw())
+            }
+        }
+
+        impl ::std::ops::BitOr for Flags {
+            type Output = Flags;
+
+            fn bitor(self, rhs: Flags) -> Flags {
+                Flags(self.0 | rhs.0)
+            }
+        }
+
+        impl ::std::ops::BitAnd for Flags {
+            type Output = Flags;
+
+            fn bitand(self, rhs: Flags) -> Flags {
+                Flags(self.0 & rhs.0)
+            }
+        }
+
+        impl ::std::ops::Sub for Flags {
+            type Output = Flags;
+
+            fn sub(self, rhs: Flags) -> Flags {
+                Flags(self.0 & !rhs.0)
+            }
+        }
+    }
+}
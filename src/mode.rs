@@ -0,0 +1,394 @@
+//! Terminal mode presets
+
+use control::CSIZE::CS8;
+use control::Char::*;
+use control::CSIZE;
+use raw::{cc_t, self};
+use traits::{Clear, Contains, Get, Set};
+use {Termios, control, input, local, output};
+
+// A preset: the state some flags must have, plus optional character size and control chars
+// NB `Termios::mode` only looks at the flags and the character size
+struct Preset {
+    input: &'static [(input::Flag, bool)],
+    output: &'static [(output::Flag, bool)],
+    control: &'static [(control::Flag, bool)],
+    local: &'static [(local::Flag, bool)],
+    csize: Option<CSIZE>,
+    chars: &'static [(control::Char, cc_t)],
+}
+
+impl Preset {
+    fn apply(&self, termios: &mut Termios) {
+        apply(termios, self.input);
+        apply(termios, self.output);
+        apply(termios, self.control);
+        apply(termios, self.local);
+
+        if let Some(csize) = self.csize {
+            termios.set(csize);
+        }
+
+        for &(char, value) in self.chars {
+            termios.cc[char] = value;
+        }
+    }
+
+    fn deviations(&self, termios: &Termios) -> Vec<Deviation> {
+        let mut deviations = vec![];
+
+        deviations.extend(self.input.iter().filter(|&&(flag, state)| {
+            termios.contains(flag) != state
+        }).map(|&(flag, state)| Deviation::Input(flag, state)));
+        deviations.extend(self.output.iter().filter(|&&(flag, state)| {
+            termios.contains(flag) != state
+        }).map(|&(flag, state)| Deviation::Output(flag, state)));
+        deviations.extend(self.control.iter().filter(|&&(flag, state)| {
+            termios.contains(flag) != state
+        }).map(|&(flag, state)| Deviation::Control(flag, state)));
+        deviations.extend(self.local.iter().filter(|&&(flag, state)| {
+            termios.contains(flag) != state
+        }).map(|&(flag, state)| Deviation::Local(flag, state)));
+
+        if let Some(csize) = self.csize {
+            if termios.get::<CSIZE>() != csize {
+                deviations.push(Deviation::CharSize(csize));
+            }
+        }
+
+        deviations
+    }
+}
+
+fn apply<T>(termios: &mut Termios, flags: &[(T, bool)]) where T: Copy, Termios: Clear<T> + Set<T> {
+    for &(flag, state) in flags {
+        if state {
+            termios.set(flag);
+        } else {
+            termios.clear(flag);
+        }
+    }
+}
+
+const RAW: Preset = Preset {
+    input: &[
+        (input::Flag::BRKINT, false),
+        (input::Flag::ICRNL, false),
+        (input::Flag::IGNBRK, false),
+        (input::Flag::IGNCR, false),
+        (input::Flag::INLCR, false),
+        (input::Flag::ISTRIP, false),
+        (input::Flag::IXON, false),
+        (input::Flag::PARMRK, false),
+    ],
+    output: &[
+        (output::Flag::OPOST, false),
+    ],
+    control: &[
+        (control::Flag::PARENB, false),
+    ],
+    local: &[
+        (local::Flag::ECHO, false),
+        (local::Flag::ECHONL, false),
+        (local::Flag::ICANON, false),
+        (local::Flag::IEXTEN, false),
+        (local::Flag::ISIG, false),
+    ],
+    csize: Some(CS8),
+    chars: &[(VMIN, 1), (VTIME, 0)],
+};
+
+const CBREAK: Preset = Preset {
+    input: &[],
+    output: &[],
+    control: &[],
+    local: &[
+        (local::Flag::ECHO, false),
+        (local::Flag::ICANON, false),
+        (local::Flag::ISIG, true),
+    ],
+    csize: None,
+    chars: &[(VMIN, 1), (VTIME, 0)],
+};
+
+// NB Same as `stty sane`, minus the platform specific flags
+const SANE: Preset = Preset {
+    input: &[
+        (input::Flag::BRKINT, true),
+        (input::Flag::ICRNL, true),
+        (input::Flag::IGNBRK, false),
+        (input::Flag::IGNCR, false),
+        (input::Flag::IMAXBEL, true),
+        (input::Flag::INLCR, false),
+        (input::Flag::IXANY, false),
+        (input::Flag::IXOFF, false),
+    ],
+    output: &[
+        (output::Flag::OCRNL, false),
+        (output::Flag::ONLCR, true),
+        (output::Flag::ONLRET, false),
+        (output::Flag::ONOCR, false),
+        (output::Flag::OPOST, true),
+    ],
+    control: &[
+        (control::Flag::CREAD, true),
+    ],
+    local: &[
+        (local::Flag::ECHOCTL, true),
+        (local::Flag::ECHOE, true),
+        (local::Flag::ECHOKE, true),
+        (local::Flag::ECHOK, true),
+        (local::Flag::ECHONL, false),
+        (local::Flag::ECHOPRT, false),
+        (local::Flag::ECHO, true),
+        (local::Flag::EXTPROC, false),
+        (local::Flag::FLUSHO, false),
+        (local::Flag::ICANON, true),
+        (local::Flag::IEXTEN, true),
+        (local::Flag::ISIG, true),
+        (local::Flag::NOFLSH, false),
+        (local::Flag::TOSTOP, false),
+    ],
+    csize: None,
+    chars: &[
+        (VDISCARD, 0o17),           // ^O
+        (VEOF, 0o4),                // ^D
+        (VEOL2, raw::VDISABLE as cc_t),
+        (VEOL, raw::VDISABLE as cc_t),
+        (VERASE, 0o177),            // ^?
+        (VINTR, 0o3),               // ^C
+        (VKILL, 0o25),              // ^U
+        (VLNEXT, 0o26),             // ^V
+        (VMIN, 1),
+        (VQUIT, 0o34),              // ^\
+        (VREPRINT, 0o22),           // ^R
+        (VSTART, 0o21),             // ^Q
+        (VSTOP, 0o23),              // ^S
+        (VSUSP, 0o32),              // ^Z
+        (VTIME, 0),
+        (VWERASE, 0o27),            // ^W
+    ],
+};
+
+// NB Only used to classify, a terminal is cooked if it's canonical, echoes, generates signals
+// and processes its output, whatever the other flags of `SANE` are
+const COOKED: Preset = Preset {
+    input: &[],
+    output: &[
+        (output::Flag::OPOST, true),
+    ],
+    control: &[],
+    local: &[
+        (local::Flag::ECHO, true),
+        (local::Flag::ICANON, true),
+        (local::Flag::ISIG, true),
+    ],
+    csize: None,
+    chars: &[],
+};
+
+/// Terminal mode, as classified by `Termios::mode`
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mode {
+    /// Same flags as set by `Termios::make_raw`
+    Raw,
+    /// Same flags as set by `Termios::make_cbreak`
+    Cbreak,
+    /// Line buffering, echo, signals and output processing (`ICANON`, `ECHO`, `ISIG` and
+    /// `OPOST`), like after `Termios::make_sane`
+    Cooked,
+    /// Doesn't match any preset, contains the deviations from the nearest one
+    Custom(Vec<Deviation>),
+}
+
+/// A difference between a `Termios` structure and a preset
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Deviation {
+    /// The input flag should be set (`true`) or cleared (`false`)
+    Input(input::Flag, bool),
+    /// The output flag should be set (`true`) or cleared (`false`)
+    Output(output::Flag, bool),
+    /// The control flag should be set (`true`) or cleared (`false`)
+    Control(control::Flag, bool),
+    /// The local flag should be set (`true`) or cleared (`false`)
+    Local(local::Flag, bool),
+    /// The character size should be this one
+    CharSize(CSIZE),
+}
+
+/// A setting that prevents the terminal from passing 8-bit data untouched
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotEightBitClean {
+    /// `ISTRIP` is set, the 8th bit of every input byte is cleared
+    Strip,
+    /// The character size is smaller than 8 bits
+    CharSize(CSIZE),
+    /// `PARENB` is set but `INPCK` is not, parity is generated but not checked
+    UncheckedParity,
+}
+
+impl Termios {
+    /// Classifies the terminal mode
+    ///
+    /// The raw and cbreak modes are based on the same definitions as the presets, the cooked mode
+    /// only on the flags that make a terminal canonical (see `Mode::Cooked`). The control chars
+    /// (e.g. `VMIN`/`VTIME`) are not considered, only the flags and the character size.
+    ///
+    /// # Examples
+    ///
+    /// ``` no_run
+    /// #![feature(libc)]
+    ///
+    /// extern crate libc;
+    /// extern crate termios;
+    ///
+    /// use termios::mode::Mode;
+    /// use termios::prelude::*;
+    ///
+    /// fn main() {
+    ///     match Termios::fetch(libc::STDIN_FILENO).unwrap().mode() {
+    ///         Mode::Raw => println!("stdin is in raw mode"),
+    ///         Mode::Cbreak => println!("stdin is in cbreak mode"),
+    ///         Mode::Cooked => println!("stdin is in cooked mode"),
+    ///         Mode::Custom(d) => println!("stdin is in a custom mode: {:?}", d),
+    ///     }
+    /// }
+    /// ```
+    pub fn mode(&self) -> Mode {
+        let raw = RAW.deviations(self);
+        let cbreak = CBREAK.deviations(self);
+        let cooked = COOKED.deviations(self);
+
+        if raw.is_empty() {
+            Mode::Raw
+        } else if cbreak.is_empty() {
+            Mode::Cbreak
+        } else if cooked.is_empty() {
+            Mode::Cooked
+        } else {
+            let mut candidates = vec![raw, cbreak, cooked];
+            candidates.sort_by(|a, b| a.len().cmp(&b.len()));
+
+            Mode::Custom(candidates.swap_remove(0))
+        }
+    }
+
+    /// Returns the settings that prevent 8-bit data from passing through the terminal untouched
+    pub fn not_eight_bit_clean(&self) -> Vec<NotEightBitClean> {
+        let mut issues = vec![];
+
+        if self.contains(input::Flag::ISTRIP) {
+            issues.push(NotEightBitClean::Strip);
+        }
+
+        match self.get::<CSIZE>() {
+            CS8 => {},
+            csize => issues.push(NotEightBitClean::CharSize(csize)),
+        }
+
+        if self.contains(control::Flag::PARENB) && !self.contains(input::Flag::INPCK) {
+            issues.push(NotEightBitClean::UncheckedParity);
+        }
+
+        issues
+    }
+
+    /// Checks if 8-bit data passes through the terminal untouched
+    pub fn is_eight_bit_clean(&self) -> bool {
+        self.not_eight_bit_clean().is_empty()
+    }
+
+    /// Puts `Termios` in "raw" mode
+    ///
+    /// Input is available byte by byte, without echo, signals, or any input/output processing,
+    /// and the character size is set to 8 bits. Unlike libc's `cfmakeraw`, this is implemented in
+    /// Rust and has the same effect on every platform.
+    ///
+    /// # Examples
+    ///
+    /// Compare "cooked" and "raw" modes
+    ///
+    /// ``` no_run
+    /// // examples/raw.rs
+    /// #![feature(libc)]
+    ///
+    /// extern crate libc;
+    /// extern crate termios;
+    ///
+    /// use termios::prelude::*;
+    ///
+    /// fn main() {
+    ///     let mut termios = Termios::fetch(libc::STDIN_FILENO).unwrap();
+    ///     println!("Cooked:\n{:?}", termios);
+    ///     termios.make_raw();
+    ///     println!("\nRaw:\n{:?}", termios);
+    /// }
+    /// ```
+    ///
+    /// ``` text
+    /// Cooked:
+    /// iflag:  ICRNL | IXON
+    /// oflag:  ONLCR | OPOST
+    /// cflag:  CS8 | CREAD
+    /// lflag:  ECHOCTL | ECHOE | ECHOKE | ECHOK | ECHO | ICANON | IEXTEN | ISIG
+    /// line:   Tty
+    /// cc:     VDISCARD: ^O, VEOF: ^D, VEOL2: undef, (..)
+    /// ispeed: B38400
+    /// ospeed: B38400
+    ///
+    /// Raw:
+    /// iflag:
+    /// oflag:  ONLCR
+    /// cflag:  CS8 | CREAD
+    /// lflag:  ECHOCTL | ECHOE | ECHOKE | ECHOK
+    /// line:   Tty
+    /// cc:     VDISCARD: ^O, VEOF: ^D, VEOL2: undef, (..)
+    /// ispeed: B38400
+    /// ospeed: B38400
+    /// ```
+    pub fn make_raw(&mut self) {
+        RAW.apply(self)
+    }
+
+    /// Puts `Termios` in "raw" mode, but keeps the output processing (`OPOST`) as it is
+    ///
+    /// Useful to keep translating `\n` into `\r\n` (`ONLCR`) on output.
+    pub fn make_raw_keep_output(&mut self) {
+        let opost = self.contains(output::Flag::OPOST);
+
+        self.make_raw();
+
+        if opost {
+            self.set(output::Flag::OPOST);
+        }
+    }
+
+    /// Puts `Termios` in "raw" mode, but keeps the signal generation (`ISIG`) as it is
+    ///
+    /// Useful to let Ctrl+C/Ctrl+Z interrupt/suspend the application.
+    pub fn make_raw_keep_signals(&mut self) {
+        let isig = self.contains(local::Flag::ISIG);
+
+        self.make_raw();
+
+        if isig {
+            self.set(local::Flag::ISIG);
+        }
+    }
+
+    /// Puts `Termios` in "cbreak" mode
+    ///
+    /// Input is available byte by byte and it's not echoed, but signals (Ctrl+C, etc) and input
+    /// and output processing stay enabled.
+    pub fn make_cbreak(&mut self) {
+        CBREAK.apply(self)
+    }
+
+    /// Puts `Termios` in "sane" mode, like `stty sane` does
+    ///
+    /// Line buffering, echo, signals and the usual input/output processing get enabled, and the
+    /// control chars get their default values (`VINTR` = `^C`, `VERASE` = `^?`, etc).
+    pub fn make_sane(&mut self) {
+        SANE.apply(self)
+    }
+}
@@ -1,22 +1,25 @@
 //! Software output processing
 
 use std::fmt;
+use std::str::FromStr;
 
-use Termios;
+use {ParseError, Termios};
 use raw::{tcflag_t, self};
 use self::Flag::*;
-use traits::{Clear, Contains, Set};
+use traits::{Clear, Contains, GetFrom, Set};
 
 /// Output flags
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
 #[repr(C)]
 pub struct Flags(tcflag_t);
 
+flags!(MASKS);
+
 impl fmt::Debug for Flags {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut is_first = true;
 
-        for &flag in &FLAGS {
+        for flag in Flag::all() {
             let value = flag.to_raw();
 
             if self.0 & value == value {
@@ -30,10 +33,83 @@ impl fmt::Debug for Flags {
             }
         }
 
+        for delay in delays(self.0) {
+            if is_first {
+                is_first = false;
+
+                try!(write!(f, "{}", delay));
+            } else {
+                try!(write!(f, " | {}", delay));
+            }
+        }
+
+        let unknown = self.unknown_bits();
+
+        if unknown != 0 {
+            if is_first {
+                try!(write!(f, "{:#x}", unknown));
+            } else {
+                try!(write!(f, " | {:#x}", unknown));
+            }
+        }
+
         Ok(())
     }
 }
 
+// Multi-bit fields
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+const MASKS: tcflag_t =
+    raw::NLDLY | raw::CRDLY | raw::TABDLY | raw::BSDLY | raw::VTDLY | raw::FFDLY;
+
+// Multi-bit fields
+#[cfg(target_os = "freebsd")]
+const MASKS: tcflag_t = raw::TABDLY;
+
+// Non-zero delays
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn delays(oflag: tcflag_t) -> Vec<String> {
+    let mut delays = vec![];
+
+    if oflag & raw::NLDLY != 0 {
+        delays.push(format!("{:?}", NewlineDelay::from_raw(oflag & raw::NLDLY)));
+    }
+
+    if oflag & raw::CRDLY != 0 {
+        delays.push(format!("{:?}", CarriageReturnDelay::from_raw(oflag & raw::CRDLY)));
+    }
+
+    if oflag & raw::TABDLY != 0 {
+        delays.push(format!("{:?}", TabDelay::from_raw(oflag & raw::TABDLY)));
+    }
+
+    if oflag & raw::BSDLY != 0 {
+        delays.push(format!("{:?}", BackspaceDelay::from_raw(oflag & raw::BSDLY)));
+    }
+
+    if oflag & raw::VTDLY != 0 {
+        delays.push(format!("{:?}", VerticalTabDelay::from_raw(oflag & raw::VTDLY)));
+    }
+
+    if oflag & raw::FFDLY != 0 {
+        delays.push(format!("{:?}", FormFeedDelay::from_raw(oflag & raw::FFDLY)));
+    }
+
+    delays
+}
+
+// Non-zero delays
+#[cfg(target_os = "freebsd")]
+fn delays(oflag: tcflag_t) -> Vec<String> {
+    let mut delays = vec![];
+
+    if oflag & raw::TABDLY != 0 {
+        delays.push(format!("{:?}", TabDelay::from_raw(oflag & raw::TABDLY)));
+    }
+
+    delays
+}
+
 const FLAGS: [Flag; 5] = [
     OCRNL,
     ONLCR,
@@ -42,33 +118,142 @@ const FLAGS: [Flag; 5] = [
     OPOST,
 ];
 
+#[cfg(target_os = "freebsd")]
+const EXTENSIONS: [Flag; 1] = [
+    ONOEOT,
+];
+
+#[cfg(target_os = "linux")]
+const EXTENSIONS: [Flag; 3] = [
+    OFDEL,
+    OFILL,
+    OLCUC,
+];
+
+#[cfg(target_os = "macos")]
+const EXTENSIONS: [Flag; 3] = [
+    OFDEL,
+    OFILL,
+    ONOEOT,
+];
+
 /// Standard output flags
-#[derive(Clone, Copy, Debug)]
+///
+/// Platform specific flags are only available on the platforms that support them, use
+/// `Flag::all()` to list the flags supported by the current target.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 pub enum Flag {
     /// Map CR to NL on output
     OCRNL,
+    /// Fill character is DEL, else NUL
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    OFDEL,
+    /// Use fill characters for delay
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    OFILL,
+    /// Map lower case to upper case on output
+    #[cfg(target_os = "linux")]
+    OLCUC,
     /// Map NL to CR-NL (ala CRMOD)
     ONLCR,
     /// NL performs CR function
     ONLRET,
     /// No CR output at column 0
     ONOCR,
+    /// Discard EOT's (`^D`) on output
+    #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+    ONOEOT,
     /// Enable following output processing
     OPOST,
 }
 
 impl Flag {
+    /// Returns all the flags supported by the current target, in the order `Debug` prints them
+    pub fn all() -> Vec<Flag> {
+        FLAGS.iter().chain(EXTENSIONS.iter()).cloned().collect()
+    }
+
+    /// Returns the name of the flag, e.g. `"ONLCR"`
+    pub fn name(&self) -> &'static str {
+        match *self {
+            OCRNL => "OCRNL",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFDEL => "OFDEL",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFILL => "OFILL",
+            #[cfg(target_os = "linux")]
+            OLCUC => "OLCUC",
+            ONLCR => "ONLCR",
+            ONLRET => "ONLRET",
+            ONOCR => "ONOCR",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ONOEOT => "ONOEOT",
+            OPOST => "OPOST",
+        }
+    }
+
+    /// Returns a short description of the flag
+    pub fn description(&self) -> &'static str {
+        match *self {
+            OCRNL => "Map CR to NL on output",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFDEL => "Fill character is DEL, else NUL",
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFILL => "Use fill characters for delay",
+            #[cfg(target_os = "linux")]
+            OLCUC => "Map lower case to upper case on output",
+            ONLCR => "Map NL to CR-NL (ala CRMOD)",
+            ONLRET => "NL performs CR function",
+            ONOCR => "No CR output at column 0",
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ONOEOT => "Discard EOT's (`^D`) on output",
+            OPOST => "Enable following output processing",
+        }
+    }
+
+    /// Checks if the flag is specified by POSIX, otherwise it's an extension
+    pub fn is_posix(&self) -> bool {
+        match *self {
+            OCRNL | ONLCR | ONLRET | ONOCR | OPOST => true,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFDEL | OFILL => true,
+            _ => false,
+        }
+    }
+
     fn to_raw(&self) -> tcflag_t {
         match *self {
             OCRNL => raw::OCRNL,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFDEL => raw::OFDEL,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            OFILL => raw::OFILL,
+            #[cfg(target_os = "linux")]
+            OLCUC => raw::OLCUC,
             ONLCR => raw::ONLCR,
             ONLRET => raw::ONLRET,
             ONOCR => raw::ONOCR,
+            #[cfg(any(target_os = "freebsd", target_os = "macos"))]
+            ONOEOT => raw::ONOEOT,
             OPOST => raw::OPOST,
         }
     }
 }
 
+impl FromStr for Flag {
+    type Err = ParseError;
+
+    /// Parses the name of the flag, case insensitively (e.g. `"onlcr"`)
+    fn from_str(s: &str) -> Result<Flag, ParseError> {
+        let name = s.to_uppercase();
+
+        match Flag::all().into_iter().find(|x| x.name() == name) {
+            Some(x) => Ok(x),
+            None => Err(ParseError::new("output flag", s)),
+        }
+    }
+}
+
 impl Clear<Flag> for Termios {
     fn clear(&mut self, flag: Flag) {
         self.oflag.0 &= !(flag.to_raw())
@@ -88,3 +273,369 @@ impl Set<Flag> for Termios {
         self.oflag.0 |= flag.to_raw()
     }
 }
+
+/// How written line endings are translated, a typed view of `OPOST`, `ONLCR` and `OCRNL`
+///
+/// `Lf` and `CrLf` are named after the line ending the device receives when the process writes
+/// `\n`. The translations only happen if `OPOST` is set: `get` reports `Unprocessed` when it's
+/// cleared, and setting any other variant sets it. `ONLRET` and `ONOCR` only tell the driver how
+/// the device moves its carriage (for the column tracking and the delays), they are left alone.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum OutputNewline {
+    /// Output processing is disabled (`OPOST` cleared), nothing is translated
+    Unprocessed,
+    /// LF, no translation
+    Lf,
+    /// CR LF, LF is translated into CR LF (`ONLCR`)
+    CrLf,
+    /// The carriage returns written by the process are translated into LF (`OCRNL`), for devices
+    /// that don't understand CR
+    CrToLf,
+    /// A combination of the above flags that doesn't describe a line ending, `ONLCR` together
+    /// with `OCRNL`
+    Conflicting(Flags),
+}
+
+const NEWLINE_MASK: tcflag_t = raw::ONLCR | raw::OCRNL;
+
+impl GetFrom<Termios> for OutputNewline {
+    fn get_from(termios: &Termios) -> OutputNewline {
+        if termios.oflag.0 & raw::OPOST == 0 {
+            return OutputNewline::Unprocessed;
+        }
+
+        match termios.oflag.0 & NEWLINE_MASK {
+            0 => OutputNewline::Lf,
+            raw::ONLCR => OutputNewline::CrLf,
+            raw::OCRNL => OutputNewline::CrToLf,
+            flags => OutputNewline::Conflicting(Flags(flags)),
+        }
+    }
+}
+
+impl Set<OutputNewline> for Termios {
+    fn set(&mut self, newline: OutputNewline) {
+        let flags = match newline {
+            OutputNewline::Unprocessed => {
+                self.oflag.0 &= !raw::OPOST;
+
+                return;
+            },
+            OutputNewline::Lf => 0,
+            OutputNewline::CrLf => raw::ONLCR,
+            OutputNewline::CrToLf => raw::OCRNL,
+            OutputNewline::Conflicting(flags) => flags.0 & NEWLINE_MASK,
+        };
+
+        self.oflag.0 &= !NEWLINE_MASK;
+        self.oflag.0 |= raw::OPOST | flags;
+    }
+}
+
+/// Newline delay
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum NewlineDelay {
+    /// No delay
+    Nl0,
+    /// Delay after a newline
+    Nl1,
+    /// Delay type 2
+    #[cfg(target_os = "macos")]
+    Nl2,
+    /// Delay type 3
+    #[cfg(target_os = "macos")]
+    Nl3,
+    /// Any other value of the field
+    Other(tcflag_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl NewlineDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            NewlineDelay::Nl0 => raw::NL0,
+            NewlineDelay::Nl1 => raw::NL1,
+            #[cfg(target_os = "macos")]
+            NewlineDelay::Nl2 => raw::NL2,
+            #[cfg(target_os = "macos")]
+            NewlineDelay::Nl3 => raw::NL3,
+            NewlineDelay::Other(delay) => delay & raw::NLDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> NewlineDelay {
+        match delay {
+            raw::NL0 => NewlineDelay::Nl0,
+            raw::NL1 => NewlineDelay::Nl1,
+            #[cfg(target_os = "macos")]
+            raw::NL2 => NewlineDelay::Nl2,
+            #[cfg(target_os = "macos")]
+            raw::NL3 => NewlineDelay::Nl3,
+            _ => NewlineDelay::Other(delay),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl GetFrom<Termios> for NewlineDelay {
+    fn get_from(termios: &Termios) -> NewlineDelay {
+        NewlineDelay::from_raw(termios.oflag.0 & raw::NLDLY)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Set<NewlineDelay> for Termios {
+    fn set(&mut self, delay: NewlineDelay) {
+        self.oflag.0 &= !raw::NLDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
+
+/// Carriage return delay
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CarriageReturnDelay {
+    /// No delay
+    Cr0,
+    /// Delay type 1
+    Cr1,
+    /// Delay type 2
+    Cr2,
+    /// Delay type 3
+    Cr3,
+    /// Any other value of the field
+    Other(tcflag_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl CarriageReturnDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            CarriageReturnDelay::Cr0 => raw::CR0,
+            CarriageReturnDelay::Cr1 => raw::CR1,
+            CarriageReturnDelay::Cr2 => raw::CR2,
+            CarriageReturnDelay::Cr3 => raw::CR3,
+            CarriageReturnDelay::Other(delay) => delay & raw::CRDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> CarriageReturnDelay {
+        match delay {
+            raw::CR0 => CarriageReturnDelay::Cr0,
+            raw::CR1 => CarriageReturnDelay::Cr1,
+            raw::CR2 => CarriageReturnDelay::Cr2,
+            raw::CR3 => CarriageReturnDelay::Cr3,
+            _ => CarriageReturnDelay::Other(delay),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl GetFrom<Termios> for CarriageReturnDelay {
+    fn get_from(termios: &Termios) -> CarriageReturnDelay {
+        CarriageReturnDelay::from_raw(termios.oflag.0 & raw::CRDLY)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Set<CarriageReturnDelay> for Termios {
+    fn set(&mut self, delay: CarriageReturnDelay) {
+        self.oflag.0 &= !raw::CRDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
+
+/// Horizontal tab delay
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TabDelay {
+    /// No delay
+    Tab0,
+    /// Delay type 1
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    Tab1,
+    /// Delay type 2
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    Tab2,
+    /// Expand tabs to spaces (a.k.a. `OXTABS`/`XTABS`)
+    Tab3,
+    /// Any other value of the field, e.g. `TAB1 | OXTABS` on macOS
+    Other(tcflag_t),
+}
+
+impl TabDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            TabDelay::Tab0 => raw::TAB0,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            TabDelay::Tab1 => raw::TAB1,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            TabDelay::Tab2 => raw::TAB2,
+            TabDelay::Tab3 => raw::TAB3,
+            TabDelay::Other(delay) => delay & raw::TABDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> TabDelay {
+        match delay {
+            raw::TAB0 => TabDelay::Tab0,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            raw::TAB1 => TabDelay::Tab1,
+            #[cfg(any(target_os = "linux", target_os = "macos"))]
+            raw::TAB2 => TabDelay::Tab2,
+            raw::TAB3 => TabDelay::Tab3,
+            _ => TabDelay::Other(delay),
+        }
+    }
+}
+
+impl GetFrom<Termios> for TabDelay {
+    fn get_from(termios: &Termios) -> TabDelay {
+        TabDelay::from_raw(termios.oflag.0 & raw::TABDLY)
+    }
+}
+
+impl Set<TabDelay> for Termios {
+    fn set(&mut self, delay: TabDelay) {
+        self.oflag.0 &= !raw::TABDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
+
+/// Backspace delay
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BackspaceDelay {
+    /// No delay
+    Bs0,
+    /// Delay after a backspace
+    Bs1,
+    /// Any other value of the field
+    Other(tcflag_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl BackspaceDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            BackspaceDelay::Bs0 => raw::BS0,
+            BackspaceDelay::Bs1 => raw::BS1,
+            BackspaceDelay::Other(delay) => delay & raw::BSDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> BackspaceDelay {
+        match delay {
+            raw::BS0 => BackspaceDelay::Bs0,
+            raw::BS1 => BackspaceDelay::Bs1,
+            _ => BackspaceDelay::Other(delay),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl GetFrom<Termios> for BackspaceDelay {
+    fn get_from(termios: &Termios) -> BackspaceDelay {
+        BackspaceDelay::from_raw(termios.oflag.0 & raw::BSDLY)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Set<BackspaceDelay> for Termios {
+    fn set(&mut self, delay: BackspaceDelay) {
+        self.oflag.0 &= !raw::BSDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
+
+/// Vertical tab delay
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VerticalTabDelay {
+    /// No delay
+    Vt0,
+    /// Delay after a vertical tab
+    Vt1,
+    /// Any other value of the field
+    Other(tcflag_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl VerticalTabDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            VerticalTabDelay::Vt0 => raw::VT0,
+            VerticalTabDelay::Vt1 => raw::VT1,
+            VerticalTabDelay::Other(delay) => delay & raw::VTDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> VerticalTabDelay {
+        match delay {
+            raw::VT0 => VerticalTabDelay::Vt0,
+            raw::VT1 => VerticalTabDelay::Vt1,
+            _ => VerticalTabDelay::Other(delay),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl GetFrom<Termios> for VerticalTabDelay {
+    fn get_from(termios: &Termios) -> VerticalTabDelay {
+        VerticalTabDelay::from_raw(termios.oflag.0 & raw::VTDLY)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Set<VerticalTabDelay> for Termios {
+    fn set(&mut self, delay: VerticalTabDelay) {
+        self.oflag.0 &= !raw::VTDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
+
+/// Form feed delay
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum FormFeedDelay {
+    /// No delay
+    Ff0,
+    /// Delay after a form feed
+    Ff1,
+    /// Any other value of the field
+    Other(tcflag_t),
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl FormFeedDelay {
+    fn to_raw(&self) -> tcflag_t {
+        match *self {
+            FormFeedDelay::Ff0 => raw::FF0,
+            FormFeedDelay::Ff1 => raw::FF1,
+            FormFeedDelay::Other(delay) => delay & raw::FFDLY,
+        }
+    }
+
+    fn from_raw(delay: tcflag_t) -> FormFeedDelay {
+        match delay {
+            raw::FF0 => FormFeedDelay::Ff0,
+            raw::FF1 => FormFeedDelay::Ff1,
+            _ => FormFeedDelay::Other(delay),
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl GetFrom<Termios> for FormFeedDelay {
+    fn get_from(termios: &Termios) -> FormFeedDelay {
+        FormFeedDelay::from_raw(termios.oflag.0 & raw::FFDLY)
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+impl Set<FormFeedDelay> for Termios {
+    fn set(&mut self, delay: FormFeedDelay) {
+        self.oflag.0 &= !raw::FFDLY;
+        self.oflag.0 |= delay.to_raw();
+    }
+}
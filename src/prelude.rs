@@ -1,5 +1,6 @@
 //! A collection of the most used structs and traits, meant to be glob imported
 
-pub use {Termios, When};
+pub use {FlowAction, Queue, Termios, When};
+pub use terminal::Terminal;
 pub use traits::{Clear, Contains, Get, Set};
 pub use {control, input, local, output};
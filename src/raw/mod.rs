@@ -11,6 +11,9 @@ pub use self::ffi::tcflag_t;
 
 pub use self::ffi::Struct_termios as Termios;
 
+#[cfg(target_os = "linux")]
+pub use self::ffi::Struct_termios2 as Termios2;
+
 pub use self::ffi::{
     cfgetispeed,
     cfgetospeed,
@@ -26,6 +29,55 @@ pub use self::ffi::{
     tcsetattr,
 };
 
+pub use self::ffi::sigset_t;
+
+pub use self::ffi::Struct_sigaction as Sigaction;
+
+pub use self::ffi::{
+    atexit,
+    getpgrp,
+    isatty,
+    pthread_sigmask,
+    raise,
+    sigaction,
+    sigaddset,
+    sigemptyset,
+    signal,
+    tcgetpgrp,
+};
+
+/// Signal handler, as stored in the `sa_handler` field of `Sigaction`
+pub type Handler = Option<extern "C" fn(::libc::c_int)>;
+
+// NB `sa_handler` is a macro that names a field of an (unnamed) union, whose name depends on the
+// platform
+/// Returns the `sa_handler` of `action`
+#[cfg(target_os = "linux")]
+pub fn sa_handler(action: &mut Sigaction) -> Handler {
+    unsafe { *action.__sigaction_handler.sa_handler() }
+}
+
+/// Changes the `sa_handler` of `action`
+#[cfg(target_os = "linux")]
+pub fn set_sa_handler(action: &mut Sigaction, handler: Handler) {
+    unsafe { *action.__sigaction_handler.sa_handler() = handler }
+}
+
+/// Returns the `sa_handler` of `action`
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub fn sa_handler(action: &mut Sigaction) -> Handler {
+    unsafe { *action.__sigaction_u.__sa_handler() }
+}
+
+/// Changes the `sa_handler` of `action`
+#[cfg(any(target_os = "freebsd", target_os = "macos"))]
+pub fn set_sa_handler(action: &mut Sigaction, handler: Handler) {
+    unsafe { *action.__sigaction_u.__sa_handler() = handler }
+}
+
+#[cfg(target_os = "linux")]
+pub use self::ffi::ioctl;
+
 #[cfg(target_os = "freebsd")]
 pub use self::ffi::{
     cfmakesane,
@@ -59,6 +111,7 @@ macro_rules! cc {
 
 cc! {
     NCCS_ = NCCS,
+    _POSIX_VDISABLE_ = VDISABLE,
     VDISCARD_ = VDISCARD,
     VEOF_ = VEOF,
     VEOL2_ = VEOL2,
@@ -156,22 +209,62 @@ oflag! {
 
 #[cfg(target_os = "linux")]
 oflag! {
+    BS0_ = BS0,
+    BS1_ = BS1,
+    BSDLY_ = BSDLY,
+    CR0_ = CR0,
+    CR1_ = CR1,
+    CR2_ = CR2,
+    CR3_ = CR3,
+    CRDLY_ = CRDLY,
+    FF0_ = FF0,
+    FF1_ = FF1,
+    FFDLY_ = FFDLY,
+    NL0_ = NL0,
+    NL1_ = NL1,
+    NLDLY_ = NLDLY,
     OFDEL_ = OFDEL,
     OFILL_ = OFILL,
     OLCUC_ = OLCUC,
+    TAB0_ = TAB0,
+    TAB1_ = TAB1,
+    TAB2_ = TAB2,
+    TAB3_ = TAB3,
+    TABDLY_ = TABDLY,
+    VT0_ = VT0,
+    VT1_ = VT1,
+    VTDLY_ = VTDLY,
 }
 
 #[cfg(target_os = "macos")]
 oflag! {
+    BS0_ = BS0,
+    BS1_ = BS1,
     BSDLY_ = BSDLY,
+    CR0_ = CR0,
+    CR1_ = CR1,
+    CR2_ = CR2,
+    CR3_ = CR3,
     CRDLY_ = CRDLY,
+    FF0_ = FF0,
+    FF1_ = FF1,
     FFDLY_ = FFDLY,
+    NL0_ = NL0,
+    NL1_ = NL1,
+    NL2_ = NL2,
+    NL3_ = NL3,
     NLDLY_ = NLDLY,
     OFDEL_ = OFDEL,
     OFILL_ = OFILL,
     ONOEOT_ = ONOEOT,
     OXTABS_ = OXTABS,
+    TAB0_ = TAB0,
+    TAB1_ = TAB1,
+    TAB2_ = TAB2,
+    TAB3_ = TAB3,
     TABDLY_ = TABDLY,
+    VT0_ = VT0,
+    VT1_ = VT1,
     VTDLY_ = VTDLY,
 }
 
@@ -210,6 +303,9 @@ cflag! {
 
 #[cfg(target_os = "linux")]
 cflag! {
+    BOTHER_ = BOTHER,
+    CBAUDEX_ = CBAUDEX,
+    CBAUD_ = CBAUD,
     CIBAUD_ = CIBAUD,
     CMSPAR_ = CMSPAR,
 }
@@ -225,6 +321,24 @@ cflag! {
     MDMBUF_ = MDMBUF,
 }
 
+#[cfg(target_os = "linux")]
+macro_rules! termios2 {
+    ($($ffi:ident = $ident:ident,)+) => {
+        $(
+            pub use self::ffi::$ffi as $ident;
+        )+
+    }
+}
+
+#[cfg(target_os = "linux")]
+termios2! {
+    IBSHIFT_ = IBSHIFT,
+    TCGETS2_ = TCGETS2,
+    TCSETS2_ = TCSETS2,
+    TCSETSF2_ = TCSETSF2,
+    TCSETSW2_ = TCSETSW2,
+}
+
 macro_rules! lflag {
     ($($ffi:ident = $ident:ident,)+) => {
         $(
@@ -386,3 +500,86 @@ baud! {
     B7200_ = B7200,
     B76800_ = B76800,
 }
+
+macro_rules! signal {
+    ($($ffi:ident = $ident:ident,)+) => {
+        $(
+            pub use self::ffi::$ffi as $ident;
+        )+
+    }
+}
+
+signal! {
+    SIGHUP_ = SIGHUP,
+    SIGINT_ = SIGINT,
+    SIGQUIT_ = SIGQUIT,
+    SIGTERM_ = SIGTERM,
+    SIGTSTP_ = SIGTSTP,
+    SIGTTOU_ = SIGTTOU,
+}
+
+macro_rules! sigprocmask {
+    ($($ffi:ident = $ident:ident,)+) => {
+        $(
+            pub use self::ffi::$ffi as $ident;
+        )+
+    }
+}
+
+sigprocmask! {
+    SIG_BLOCK_ = SIG_BLOCK,
+    SIG_SETMASK_ = SIG_SETMASK,
+    SIG_UNBLOCK_ = SIG_UNBLOCK,
+}
+
+macro_rules! sighandler {
+    ($($ffi:ident = $ident:ident,)+) => {
+        $(
+            pub use self::ffi::$ffi as $ident;
+        )+
+    }
+}
+
+sighandler! {
+    SIG_IGN_ = SIG_IGN,
+}
+
+#[cfg(target_os = "linux")]
+macro_rules! ldisc {
+    ($($ffi:ident = $ident:ident,)+) => {
+        $(
+            pub use self::ffi::$ffi as $ident;
+        )+
+    }
+}
+
+#[cfg(target_os = "linux")]
+ldisc! {
+    N_6PACK_ = N_6PACK,
+    N_AX25_ = N_AX25,
+    N_CAIF_ = N_CAIF,
+    N_GIGASET_M101_ = N_GIGASET_M101,
+    N_GSM0710_ = N_GSM0710,
+    N_HCI_ = N_HCI,
+    N_HDLC_ = N_HDLC,
+    N_IRDA_ = N_IRDA,
+    N_MASC_ = N_MASC,
+    N_MOUSE_ = N_MOUSE,
+    N_PPP_ = N_PPP,
+    N_PPS_ = N_PPS,
+    N_PROFIBUS_FDL_ = N_PROFIBUS_FDL,
+    N_R3964_ = N_R3964,
+    N_SLCAN_ = N_SLCAN,
+    N_SLIP_ = N_SLIP,
+    N_SMSBLOCK_ = N_SMSBLOCK,
+    N_STRIP_ = N_STRIP,
+    N_SYNC_PPP_ = N_SYNC_PPP,
+    N_TI_WL_ = N_TI_WL,
+    N_TRACEROUTER_ = N_TRACEROUTER,
+    N_TRACESINK_ = N_TRACESINK,
+    N_TTY_ = N_TTY,
+    N_V253_ = N_V253,
+    N_X25_ = N_X25,
+    TIOCGETD_ = TIOCGETD,
+    TIOCSETD_ = TIOCSETD,
+}
@@ -0,0 +1,311 @@
+//! Process-wide restoration of the terminal
+//!
+//! A `TermiosGuard` only helps if its scope is left, it doesn't help if the process is killed by
+//! a signal or terminated with `process::exit`. After calling `install`, every file descriptor
+//! changed through `Termios::update` gets its original `Termios` structure saved in a global
+//! registry, which is restored:
+//!
+//! - when a thread panics,
+//! - when the process exits (`atexit`), and
+//! - when the process receives `SIGHUP`, `SIGINT`, `SIGQUIT` or `SIGTERM`. After restoring the
+//!   terminal, the signal is raised again with the action that was installed before `install`
+//!   (usually the default action). Signals that were ignored are left alone.
+//!
+//! Along with the `Termios` structure, the device of the file descriptor is saved. If the file
+//! descriptor is closed and its number reused for another file, the structure is not restored.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! #![feature(libc)]
+//!
+//! extern crate libc;
+//! extern crate termios;
+//!
+//! use termios::prelude::*;
+//! use termios::restore;
+//!
+//! fn main() {
+//!     restore::install();
+//!
+//!     let mut termios = Termios::fetch(libc::STDIN_FILENO).unwrap();
+//!     termios.make_raw();
+//!     termios.update(libc::STDIN_FILENO, When::Now).unwrap();
+//!
+//!     // The terminal will be restored even if this panics
+//!     panic!("oops");
+//! }
+//! ```
+
+use libc::{self, c_int, dev_t};
+use std::cell::UnsafeCell;
+use std::sync::atomic::{ATOMIC_BOOL_INIT, AtomicBool, AtomicUsize, Ordering};
+use std::sync::{ONCE_INIT, Once};
+use std::{mem, panic, ptr, slice};
+
+use {Error, Termios, set_attr};
+use raw;
+
+/// Maximum number of file descriptors that can be tracked
+pub const CAPACITY: usize = 8;
+
+const EMPTY: usize = 0;
+const BUSY: usize = 1;
+const READY: usize = 2;
+
+const SIGNALS: [c_int; 4] = [raw::SIGHUP, raw::SIGINT, raw::SIGQUIT, raw::SIGTERM];
+
+static INSTALL: Once = ONCE_INIT;
+static INSTALLED: AtomicBool = ATOMIC_BOOL_INIT;
+// NB Makes looking up and claiming a slot atomic, so a file descriptor can't be tracked twice
+static LOCK: AtomicBool = ATOMIC_BOOL_INIT;
+// NB Written once, in `install`, before any handler is registered and before `INSTALLED` is set
+static mut SLOTS: *const Slot = 0 as *const Slot;
+// NB The actions that were installed for `SIGNALS` before `install`, same order. Written once,
+// in `install`, before the corresponding handler is registered
+static mut PREVIOUS: *mut raw::Sigaction = 0 as *mut raw::Sigaction;
+
+// NB A slot is only read by the handlers while `READY`, and only written while `BUSY`
+struct Slot {
+    state: AtomicUsize,
+    fd: UnsafeCell<c_int>,
+    device: UnsafeCell<Device>,
+    termios: UnsafeCell<raw::Termios>,
+}
+
+// Identifies the file a file descriptor refers to, to detect reused file descriptor numbers
+#[derive(Clone, Copy, Eq, PartialEq)]
+struct Device {
+    dev: dev_t,
+    rdev: dev_t,
+}
+
+// NB This function is async-signal-safe (`fstat` is)
+fn device(fd: c_int) -> Option<Device> {
+    unsafe {
+        let mut stat: libc::stat = mem::zeroed();
+
+        if libc::fstat(fd, &mut stat) == 0 {
+            Some(Device { dev: stat.st_dev, rdev: stat.st_rdev })
+        } else {
+            None
+        }
+    }
+}
+
+// Checks if `slot` saved the structure of the file currently referred to by `fd`
+fn matches(slot: &Slot, fd: c_int, device: Device) -> bool {
+    unsafe { *slot.fd.get() == fd && *slot.device.get() == device }
+}
+
+fn slots() -> &'static [Slot] {
+    unsafe {
+        if SLOTS.is_null() {
+            &[]
+        } else {
+            slice::from_raw_parts(SLOTS, CAPACITY)
+        }
+    }
+}
+
+/// Installs the panic hook, the `atexit` handler and the signal handlers that restore the
+/// tracked terminals
+///
+/// Calling this function more than once has no effect.
+pub fn install() {
+    INSTALL.call_once(|| {
+        let slots: Vec<_> = (0..CAPACITY).map(|_| Slot {
+            state: AtomicUsize::new(EMPTY),
+            fd: UnsafeCell::new(0),
+            device: UnsafeCell::new(Device { dev: 0, rdev: 0 }),
+            termios: UnsafeCell::new(Default::default()),
+        }).collect();
+        let mut previous: Vec<raw::Sigaction> = SIGNALS.iter().map(|_| {
+            Default::default()
+        }).collect();
+
+        unsafe {
+            SLOTS = slots.as_ptr();
+            PREVIOUS = previous.as_mut_ptr();
+        }
+        mem::forget(slots);
+        mem::forget(previous);
+
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            restore_all();
+            previous(info);
+        }));
+
+        unsafe {
+            raw::atexit(Some(at_exit));
+
+            for (i, &signal) in SIGNALS.iter().enumerate() {
+                let previous = &mut *PREVIOUS.offset(i as isize);
+
+                raw::sigaction(signal, ptr::null(), previous);
+
+                // NB `SIG_IGN` is a sentinel address, not a function
+                let handler: usize = mem::transmute(raw::sa_handler(previous));
+
+                // An ignored signal stays ignored (e.g. `SIGINT` in a background job)
+                if handler == raw::SIG_IGN as usize {
+                    continue;
+                }
+
+                let mut action: raw::Sigaction = Default::default();
+                raw::sigemptyset(&mut action.sa_mask);
+                raw::set_sa_handler(&mut action, Some(on_signal));
+                raw::sigaction(signal, &action, ptr::null_mut());
+            }
+        }
+
+        INSTALLED.store(true, Ordering::SeqCst);
+    })
+}
+
+/// Checks if `install` has been called
+pub fn is_installed() -> bool {
+    INSTALLED.load(Ordering::SeqCst)
+}
+
+/// Saves the current `Termios` structure of `fd`, so it can be restored later
+///
+/// `Termios::update` calls this function for you once `install` has been called, but ignores its
+/// errors. Tracking a file descriptor that is already tracked has no effect, the first saved
+/// structure is kept, unless the file descriptor now refers to another device (i.e. it was
+/// closed and its number reused). Returns `Error::RegistryFull` if `CAPACITY` file descriptors
+/// are already tracked.
+pub fn track(fd: c_int) -> Result<(), Error> {
+    if !is_installed() {
+        return Ok(());
+    }
+
+    let device = match device(fd) {
+        Some(device) => device,
+        None => return Err(Error::last_os_error()),
+    };
+
+    lock();
+
+    // NB The `fd` and `device` of a `BUSY` slot are written while holding the lock, so they can
+    // be read here
+    for slot in slots() {
+        if slot.state.load(Ordering::SeqCst) == READY && unsafe { *slot.fd.get() } == fd &&
+           !matches(slot, fd, device) {
+            // Stale, the file descriptor it saved has been closed
+            slot.state.compare_and_swap(READY, EMPTY, Ordering::SeqCst);
+        }
+    }
+
+    let tracked = slots().iter().any(|slot| {
+        slot.state.load(Ordering::SeqCst) != EMPTY && matches(slot, fd, device)
+    });
+    let claimed = if tracked {
+        None
+    } else {
+        slots().iter().find(|slot| {
+            slot.state.compare_and_swap(EMPTY, BUSY, Ordering::SeqCst) == EMPTY
+        })
+    };
+
+    if let Some(slot) = claimed {
+        unsafe {
+            *slot.fd.get() = fd;
+            *slot.device.get() = device;
+        }
+    }
+
+    unlock();
+
+    let slot = match claimed {
+        Some(slot) => slot,
+        None if tracked => return Ok(()),
+        None => return Err(Error::RegistryFull),
+    };
+
+    match Termios::fetch(fd) {
+        Ok(termios) => {
+            unsafe {
+                *slot.termios.get() = termios.into_raw();
+            }
+
+            slot.state.store(READY, Ordering::SeqCst);
+
+            Ok(())
+        },
+        Err(e) => {
+            slot.state.store(EMPTY, Ordering::SeqCst);
+
+            Err(e)
+        },
+    }
+}
+
+// NB Only `track` takes this lock, the handlers never block on it
+fn lock() {
+    while LOCK.compare_and_swap(false, true, Ordering::Acquire) {}
+}
+
+fn unlock() {
+    LOCK.store(false, Ordering::Release);
+}
+
+/// Checks if `fd` is being tracked
+pub fn is_tracked(fd: c_int) -> bool {
+    let device = match device(fd) {
+        Some(device) => device,
+        None => return false,
+    };
+
+    slots().iter().any(|slot| {
+        slot.state.load(Ordering::SeqCst) == READY && matches(slot, fd, device)
+    })
+}
+
+/// Stops tracking `fd`, its saved `Termios` structure won't be restored
+pub fn forget(fd: c_int) {
+    for slot in slots() {
+        if slot.state.load(Ordering::SeqCst) == READY && unsafe { *slot.fd.get() } == fd {
+            slot.state.compare_and_swap(READY, EMPTY, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Restores the saved `Termios` structure of every tracked file descriptor
+///
+/// File descriptors that no longer refer to the device they referred to when tracked are
+/// skipped. This function is async-signal-safe. The file descriptors remain tracked.
+pub fn restore_all() {
+    for slot in slots() {
+        if slot.state.load(Ordering::SeqCst) == READY {
+            unsafe {
+                let fd = *slot.fd.get();
+
+                if device(fd) == Some(*slot.device.get()) {
+                    set_attr(fd, raw::TCSANOW, &*slot.termios.get());
+                }
+            }
+        }
+    }
+}
+
+extern "C" fn at_exit() {
+    restore_all()
+}
+
+extern "C" fn on_signal(signal: c_int) {
+    restore_all();
+
+    // Re-raise the signal with the action that was installed before ours, the signal is blocked
+    // until this handler returns
+    unsafe {
+        for (i, &s) in SIGNALS.iter().enumerate() {
+            if s == signal {
+                raw::sigaction(signal, PREVIOUS.offset(i as isize), ptr::null_mut());
+            }
+        }
+
+        raw::raise(signal);
+    }
+}
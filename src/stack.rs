@@ -0,0 +1,107 @@
+//! Stack of terminal modes
+//!
+//! Nested components of an application (e.g. a prompt inside a pager inside a REPL) can each
+//! `push` their own mode, and `pop` it when they are done, which reapplies the mode of the
+//! enclosing component.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! #![feature(libc)]
+//!
+//! extern crate libc;
+//! extern crate termios;
+//!
+//! use termios::prelude::*;
+//! use termios::stack::Stack;
+//!
+//! fn main() {
+//!     let mut stack = Stack::new(libc::STDIN_FILENO).unwrap();
+//!
+//!     let mut pager = *stack.top();
+//!     pager.clear(local::Flag::ICANON);
+//!     let pager = stack.push(pager).unwrap();
+//!
+//!     let mut prompt = *stack.top();
+//!     prompt.clear(local::Flag::ECHO);
+//!     let prompt = stack.push(prompt).unwrap();
+//!
+//!     // Popping `pager` first would be an error
+//!     stack.pop(&prompt).unwrap();
+//!     stack.pop(&pager).unwrap();
+//! }
+//! ```
+
+use libc::c_int;
+use std::sync::atomic::{ATOMIC_USIZE_INIT, AtomicUsize, Ordering};
+
+use {Error, Termios, When};
+
+static NEXT_ID: AtomicUsize = ATOMIC_USIZE_INIT;
+
+/// A per file descriptor stack of `Termios` structures
+pub struct Stack {
+    fd: c_int,
+    base: Termios,
+    modes: Vec<(usize, Termios)>,
+}
+
+/// Handle to a mode pushed onto a `Stack`, required to pop it
+#[must_use]
+#[derive(Debug)]
+pub struct Handle {
+    id: usize,
+}
+
+impl Stack {
+    /// Creates an empty stack, the current `Termios` structure of `fd` becomes its base
+    pub fn new(fd: c_int) -> Result<Stack, Error> {
+        Ok(Stack {
+            fd: fd,
+            base: try!(Termios::fetch(fd)),
+            modes: vec![],
+        })
+    }
+
+    /// Returns the file descriptor managed by this stack
+    pub fn fd(&self) -> c_int {
+        self.fd
+    }
+
+    /// Returns the number of pushed modes
+    pub fn depth(&self) -> usize {
+        self.modes.len()
+    }
+
+    /// Returns the mode at the top of the stack, or the base if no mode has been pushed
+    pub fn top(&self) -> &Termios {
+        self.modes.last().map(|&(_, ref termios)| termios).unwrap_or(&self.base)
+    }
+
+    /// Applies `termios` to the file descriptor and pushes it onto the stack
+    pub fn push(&mut self, termios: Termios) -> Result<Handle, Error> {
+        try!(termios.update(self.fd, When::Now));
+
+        let id = NEXT_ID.fetch_add(1, Ordering::SeqCst);
+        self.modes.push((id, termios));
+
+        Ok(Handle { id: id })
+    }
+
+    /// Pops the mode associated to `handle` and reapplies the previous mode
+    ///
+    /// Returns an `Error::OutOfOrder` error if the mode is not at the top of the stack, in which
+    /// case the stack is left untouched and `handle` can be popped later.
+    pub fn pop(&mut self, handle: &Handle) -> Result<Termios, Error> {
+        match self.modes.last() {
+            Some(&(id, _)) if id == handle.id => {},
+            _ => return Err(Error::OutOfOrder),
+        }
+
+        let depth = self.modes.len();
+        let previous = if depth > 1 { self.modes[depth - 2].1 } else { self.base };
+        try!(previous.update(self.fd, When::Now));
+
+        Ok(self.modes.pop().unwrap().1)
+    }
+}
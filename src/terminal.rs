@@ -0,0 +1,232 @@
+//! Termios operations on file-like types
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! extern crate termios;
+//!
+//! use std::io;
+//!
+//! use termios::prelude::*;
+//!
+//! fn main() {
+//!     let stdin = io::stdin();
+//!
+//!     if stdin.is_terminal() {
+//!         let mut termios = stdin.termios().unwrap();
+//!         termios.clear(local::Flag::ECHO);
+//!         stdin.set_termios(&termios, When::Now).unwrap();
+//!     }
+//! }
+//! ```
+//!
+//! Prompt for a password even if the standard streams are redirected.
+//!
+//! ``` no_run
+//! extern crate termios;
+//!
+//! use std::io::{BufRead, BufReader, Write};
+//!
+//! use termios::prelude::*;
+//! use termios::terminal::ControllingTerminal;
+//!
+//! fn main() {
+//!     let mut tty = ControllingTerminal::open().unwrap();
+//!     let echo = tty.termios().unwrap();
+//!     let mut no_echo = echo;
+//!     no_echo.clear(local::Flag::ECHO);
+//!
+//!     tty.set_termios(&no_echo, When::AfterFlush).unwrap();
+//!     write!(tty, "Password: ").unwrap();
+//!
+//!     let mut password = String::new();
+//!     BufReader::new(&mut tty).read_line(&mut password).unwrap();
+//!
+//!     tty.set_termios(&echo, When::Now).unwrap();
+//!     writeln!(tty, "").unwrap();
+//! }
+//! ```
+
+use libc::{self, c_int};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::os::unix::io::AsRawFd;
+use std::process::{Command, ExitStatus};
+
+use {Error, FlowAction, Queue, Termios, When};
+#[cfg(target_os = "linux")]
+use discipline::{self, LineDiscipline};
+use guard::TermiosGuard;
+use job::{self, Background};
+use raw;
+use restore;
+use stack::Stack;
+use verify::OnMismatch;
+
+// NB This is what `ctermid` returns on Linux, FreeBSD and macOS
+const PATH: &'static str = "/dev/tty";
+
+/// Extension trait for the types that wrap a file descriptor (`Stdin`, `File`, etc)
+///
+/// Every operation of this crate that takes a file descriptor is available here as well.
+pub trait Terminal: AsRawFd {
+    /// Returns the `Termios` structure associated with this terminal
+    fn termios(&self) -> Result<Termios, Error> {
+        Termios::fetch(self.as_raw_fd())
+    }
+
+    /// Applies `termios` to this terminal, see `Termios::update`
+    fn set_termios(&self, termios: &Termios, when: When) -> Result<(), Error> {
+        termios.update(self.as_raw_fd(), when)
+    }
+
+    /// Applies `termios` to this terminal from a background process group, see
+    /// `Termios::update_background`
+    fn set_termios_background(&self, termios: &Termios, when: When, background: Background)
+                              -> Result<(), Error> {
+        termios.update_background(self.as_raw_fd(), when, background)
+    }
+
+    /// Applies `termios` to this terminal and checks that every change was applied, see
+    /// `Termios::update_verified`
+    fn set_termios_verified(&self, termios: &Termios, when: When, on_mismatch: OnMismatch)
+                            -> Result<(), Error> {
+        termios.update_verified(self.as_raw_fd(), when, on_mismatch)
+    }
+
+    /// Modifies the `Termios` structure of this terminal until the returned guard is dropped,
+    /// see `Termios::modify`
+    fn modify_termios<F>(&self, f: F) -> Result<TermiosGuard, Error>
+        where F: FnOnce(&mut Termios)
+    {
+        Termios::modify(self.as_raw_fd(), f)
+    }
+
+    /// Waits until all the output written to this terminal has been transmitted, see
+    /// `Termios::drain`
+    fn drain(&self) -> Result<(), Error> {
+        Termios::drain(self.as_raw_fd())
+    }
+
+    /// Discards the data in the `queue` of this terminal, see `Termios::flush`
+    ///
+    /// Not named `flush` to not clash with `io::Write::flush`, which transmits the data instead.
+    fn discard_queue(&self, queue: Queue) -> Result<(), Error> {
+        Termios::flush(self.as_raw_fd(), queue)
+    }
+
+    /// Suspends/restarts the transmission or reception of data, see `Termios::flow`
+    fn flow(&self, action: FlowAction) -> Result<(), Error> {
+        Termios::flow(self.as_raw_fd(), action)
+    }
+
+    /// Transmits a break, see `Termios::send_break`
+    fn send_break(&self, duration: c_int) -> Result<(), Error> {
+        Termios::send_break(self.as_raw_fd(), duration)
+    }
+
+    /// Saves the current `Termios` structure of this terminal so it can be restored at exit, see
+    /// `restore::track`
+    fn track_termios(&self) -> Result<(), Error> {
+        restore::track(self.as_raw_fd())
+    }
+
+    /// Checks if the `Termios` structure of this terminal is saved, see `restore::is_tracked`
+    fn is_termios_tracked(&self) -> bool {
+        restore::is_tracked(self.as_raw_fd())
+    }
+
+    /// Stops tracking this terminal, see `restore::forget`
+    fn forget_termios(&self) {
+        restore::forget(self.as_raw_fd())
+    }
+
+    /// Creates a stack of modes for this terminal, see `stack::Stack::new`
+    fn stack(&self) -> Result<Stack, Error> {
+        Stack::new(self.as_raw_fd())
+    }
+
+    /// Suspends the process, restoring `saved` on this terminal meanwhile, see `job::suspend`
+    fn suspend<F>(&self, saved: &Termios, redraw: F) -> Result<(), Error> where F: FnOnce() {
+        job::suspend(self.as_raw_fd(), saved, redraw)
+    }
+
+    /// Hands this terminal over to `command`, see `job::run`
+    fn run(&self, command: &mut Command, saved: &Termios, active: &Termios)
+           -> Result<ExitStatus, Error> {
+        job::run(command, self.as_raw_fd(), saved, active)
+    }
+
+    /// Checks if the process is in the foreground process group of this terminal, see
+    /// `job::is_foreground`
+    fn is_foreground(&self) -> Result<bool, Error> {
+        job::is_foreground(self.as_raw_fd())
+    }
+
+    /// Returns the line discipline attached to this terminal, see `discipline::fetch`
+    #[cfg(target_os = "linux")]
+    fn line_discipline(&self) -> Result<LineDiscipline, Error> {
+        discipline::fetch(self.as_raw_fd())
+    }
+
+    /// Attaches the line discipline `line` to this terminal, see `discipline::update`
+    #[cfg(target_os = "linux")]
+    fn set_line_discipline(&self, line: LineDiscipline) -> Result<(), Error> {
+        discipline::update(self.as_raw_fd(), line)
+    }
+
+    /// Checks if the file descriptor refers to a terminal
+    fn is_terminal(&self) -> bool {
+        unsafe {
+            raw::isatty(self.as_raw_fd()) == 1
+        }
+    }
+}
+
+impl<T> Terminal for T where T: AsRawFd {}
+
+/// Read/write handle to the controlling terminal of the process (`/dev/tty`)
+///
+/// Useful to interact with the user (e.g. to prompt for a password) even when the standard
+/// streams are redirected. Its `Termios` structure is available through the `Terminal` trait.
+#[derive(Debug)]
+pub struct ControllingTerminal {
+    file: File,
+}
+
+impl ControllingTerminal {
+    /// Opens the controlling terminal for reading and writing
+    ///
+    /// Returns `Error::NoControllingTerminal` if the process doesn't have one (e.g. a daemon)
+    pub fn open() -> Result<ControllingTerminal, Error> {
+        match OpenOptions::new().read(true).write(true).open(PATH) {
+            Ok(file) => Ok(ControllingTerminal { file: file }),
+            Err(ref e) if e.raw_os_error() == Some(libc::ENXIO) => {
+                Err(Error::NoControllingTerminal)
+            },
+            Err(e) => Err(Error::from(e)),
+        }
+    }
+}
+
+impl AsRawFd for ControllingTerminal {
+    fn as_raw_fd(&self) -> c_int {
+        self.file.as_raw_fd()
+    }
+}
+
+impl Read for ControllingTerminal {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.file.read(buf)
+    }
+}
+
+impl Write for ControllingTerminal {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.file.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
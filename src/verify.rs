@@ -0,0 +1,95 @@
+//! Verified updates
+
+use libc::c_int;
+
+use {Error, Termios, When};
+
+/// A field of the `Termios` structure
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    /// Input flags
+    IFlag,
+    /// Output flags
+    OFlag,
+    /// Control flags
+    CFlag,
+    /// Local flags
+    LFlag,
+    /// Control characters
+    Cc,
+    /// Input baud rate
+    ISpeed,
+    /// Output baud rate
+    OSpeed,
+}
+
+/// What `Termios::update_verified` does when some of the requested changes didn't take
+#[derive(Clone, Copy)]
+pub enum OnMismatch {
+    /// Leave the terminal as it is
+    Keep,
+    /// Restore the `Termios` structure the terminal had before the update
+    Rollback,
+}
+
+impl Termios {
+    /// Like `update`, but checks that every requested change was applied
+    ///
+    /// POSIX only requires `tcsetattr` to fail if *none* of the requested changes could be
+    /// applied, i.e. `update` may succeed even if, for example, a baud rate was silently
+    /// dropped. This method fetches the `Termios` structure after the update and returns an
+    /// `Error::Unapplied` error that lists the fields that differ from `self`.
+    pub fn update_verified(&self, fd: c_int, when: When, on_mismatch: OnMismatch)
+                           -> Result<(), Error> {
+        let previous = try!(Termios::fetch(fd));
+
+        try!(self.update(fd, when));
+
+        let fields = self.mismatches(&try!(Termios::fetch(fd)));
+
+        if fields.is_empty() {
+            return Ok(());
+        }
+
+        if let OnMismatch::Rollback = on_mismatch {
+            try!(previous.update(fd, When::Now));
+        }
+
+        Err(Error::Unapplied(fields))
+    }
+
+    fn mismatches(&self, other: &Termios) -> Vec<Field> {
+        let (lhs, rhs) = (self.as_raw(), other.as_raw());
+        let mut fields = vec![];
+
+        if lhs.c_iflag != rhs.c_iflag {
+            fields.push(Field::IFlag);
+        }
+
+        if lhs.c_oflag != rhs.c_oflag {
+            fields.push(Field::OFlag);
+        }
+
+        if lhs.c_cflag != rhs.c_cflag {
+            fields.push(Field::CFlag);
+        }
+
+        if lhs.c_lflag != rhs.c_lflag {
+            fields.push(Field::LFlag);
+        }
+
+        if lhs.c_cc != rhs.c_cc {
+            fields.push(Field::Cc);
+        }
+
+        if self.ispeed().ok() != other.ispeed().ok() {
+            fields.push(Field::ISpeed);
+        }
+
+        if self.ospeed().ok() != other.ospeed().ok() {
+            fields.push(Field::OSpeed);
+        }
+
+        fields
+    }
+}
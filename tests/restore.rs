@@ -0,0 +1,45 @@
+//! `restore::install` changes process-wide state (panic hook, `atexit` and signal handlers), so
+//! these tests run in their own process
+
+extern crate termios;
+
+use std::fs::{File, OpenOptions};
+use std::os::unix::io::AsRawFd;
+
+use termios::prelude::*;
+use termios::restore::{self, CAPACITY};
+
+fn open_pty() -> File {
+    OpenOptions::new().read(true).write(true).open("/dev/ptmx").unwrap()
+}
+
+#[test]
+fn restore() {
+    restore::install();
+
+    let ptys: Vec<File> = (0..CAPACITY + 1).map(|_| open_pty()).collect();
+
+    // A full registry doesn't make the updates fail
+    for pty in &ptys {
+        let fd = pty.as_raw_fd();
+
+        Termios::fetch(fd).unwrap().update(fd, When::Now).unwrap();
+    }
+    assert!(ptys.iter().any(|pty| !restore::is_tracked(pty.as_raw_fd())));
+
+    for pty in &ptys {
+        restore::forget(pty.as_raw_fd());
+    }
+
+    // A file descriptor number reused by another file is not tracked
+    let pty = open_pty();
+    let fd = pty.as_raw_fd();
+    restore::track(fd).unwrap();
+    assert!(restore::is_tracked(fd));
+    drop(pty);
+
+    let file = File::open("/dev/null").unwrap();
+    assert_eq!(file.as_raw_fd(), fd);
+    assert!(!restore::is_tracked(fd));
+    restore::forget(fd);
+}